@@ -0,0 +1,106 @@
+//! Platform admin commands. The keypair must be the platform admin, or the
+//! program's upgrade authority for `init`.
+
+use anyhow::Result;
+use audio_token_platform_client::instructions;
+use audio_token_platform_client::program::UpdatePlatformConfigParams;
+use clap::{Args, Subcommand};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+
+use crate::amount::parse_amount;
+use crate::rpc::Rpc;
+
+#[derive(Subcommand)]
+pub enum AdminCommand {
+    /// Create the platform config
+    Init {
+        #[arg(long)]
+        trade_fee_bps: u64,
+        /// SOL charged for each token created
+        #[arg(long, value_parser = parse_amount)]
+        creation_fee: u64,
+        #[arg(long)]
+        fee_recipient: Pubkey,
+        /// Real SOL reserves at which a curve graduates
+        #[arg(long, value_parser = parse_amount)]
+        graduation_threshold: u64,
+    },
+    /// Change platform settings; omitted settings are left unchanged
+    Update(UpdateArgs),
+    /// Propose a new admin, who completes the transfer with `accept-admin`
+    ProposeAdmin { new_admin: Pubkey },
+    /// Accept a pending admin transfer to the keypair
+    AcceptAdmin,
+    /// Halt trading on one token
+    Halt { mint: Pubkey },
+    /// Resume trading on a halted token
+    Resume { mint: Pubkey },
+}
+
+#[derive(Args)]
+pub struct UpdateArgs {
+    #[arg(long)]
+    trade_fee_bps: Option<u64>,
+    #[arg(long)]
+    lp_fee_share_bps: Option<u64>,
+    #[arg(long)]
+    creator_fee_share_bps: Option<u64>,
+    #[arg(long)]
+    max_creator_fee_bps: Option<u64>,
+    #[arg(long, value_parser = parse_amount)]
+    creation_fee: Option<u64>,
+    #[arg(long)]
+    fee_recipient: Option<Pubkey>,
+    #[arg(long, value_parser = parse_amount)]
+    graduation_threshold: Option<u64>,
+    #[arg(long)]
+    trading_paused: Option<bool>,
+    #[arg(long)]
+    creation_paused: Option<bool>,
+}
+
+impl From<&UpdateArgs> for UpdatePlatformConfigParams {
+    fn from(args: &UpdateArgs) -> Self {
+        UpdatePlatformConfigParams {
+            trade_fee_bps: args.trade_fee_bps,
+            lp_fee_share_bps: args.lp_fee_share_bps,
+            creator_fee_share_bps: args.creator_fee_share_bps,
+            max_creator_fee_bps: args.max_creator_fee_bps,
+            creation_fee: args.creation_fee,
+            fee_recipient: args.fee_recipient,
+            graduation_threshold: args.graduation_threshold,
+            trading_paused: args.trading_paused,
+            creation_paused: args.creation_paused,
+        }
+    }
+}
+
+pub fn run(rpc: &Rpc, command: &AdminCommand) -> Result<()> {
+    let payer = rpc.payer()?;
+    let admin = payer.pubkey();
+    let instruction = match command {
+        AdminCommand::Init {
+            trade_fee_bps,
+            creation_fee,
+            fee_recipient,
+            graduation_threshold,
+        } => instructions::initialize_platform(
+            &admin,
+            &admin,
+            *trade_fee_bps,
+            *creation_fee,
+            fee_recipient,
+            *graduation_threshold,
+        ),
+        AdminCommand::Update(args) => instructions::update_platform_config(&admin, args.into()),
+        AdminCommand::ProposeAdmin { new_admin } => instructions::propose_admin(&admin, new_admin),
+        AdminCommand::AcceptAdmin => instructions::accept_admin(&admin),
+        AdminCommand::Halt { mint } => instructions::set_trading_halted(&admin, mint, true),
+        AdminCommand::Resume { mint } => instructions::set_trading_halted(&admin, mint, false),
+    };
+    let signature = rpc.send(&payer, &[instruction], &[])?;
+
+    println!("Signature {signature}");
+    Ok(())
+}
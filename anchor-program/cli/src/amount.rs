@@ -0,0 +1,89 @@
+//! Decimal SOL and token amounts on the command line.
+//!
+//! SOL and every platform token have 9 decimals, so both parse and print
+//! the same way: `1.5` is 1_500_000_000 lamports or base units.
+
+pub const DECIMALS: u32 = 9;
+
+/// Decimals of a `spot_price`, which is scaled by 10^18 per base unit.
+const PRICE_DECIMALS: u32 = 18;
+
+/// Parses a decimal amount into base units, for use as a clap `value_parser`.
+pub fn parse_amount(amount: &str) -> Result<u64, String> {
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return Err("empty amount".to_string());
+    }
+    if fraction.len() > DECIMALS as usize {
+        return Err(format!("more than {DECIMALS} decimal places"));
+    }
+    if !whole
+        .chars()
+        .chain(fraction.chars())
+        .all(|c| c.is_ascii_digit())
+    {
+        return Err(format!("invalid amount `{amount}`"));
+    }
+
+    let parse = |digits: &str| -> Result<u64, String> {
+        if digits.is_empty() {
+            Ok(0)
+        } else {
+            digits.parse().map_err(|_| "amount too large".to_string())
+        }
+    };
+    let fraction = parse(fraction)? * 10u64.pow(DECIMALS - fraction.len() as u32);
+    parse(whole)?
+        .checked_mul(10u64.pow(DECIMALS))
+        .and_then(|whole| whole.checked_add(fraction))
+        .ok_or_else(|| "amount too large".to_string())
+}
+
+/// Formats base units as a decimal amount without trailing zeros.
+pub fn format_amount(amount: u64) -> String {
+    format_fixed(amount as u128, DECIMALS)
+}
+
+/// Formats a `spot_price` as SOL per whole token.
+pub fn format_price(spot_price: u128) -> String {
+    format_fixed(spot_price, PRICE_DECIMALS)
+}
+
+fn format_fixed(value: u128, decimals: u32) -> String {
+    let scale = 10u128.pow(decimals);
+    let (whole, fraction) = (value / scale, value % scale);
+    if fraction == 0 {
+        return whole.to_string();
+    }
+    let fraction = format!("{fraction:0width$}", width = decimals as usize);
+    format!("{whole}.{}", fraction.trim_end_matches('0'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_decimal_amounts() {
+        assert_eq!(parse_amount("1"), Ok(1_000_000_000));
+        assert_eq!(parse_amount("0.5"), Ok(500_000_000));
+        assert_eq!(parse_amount(".000000001"), Ok(1));
+        assert_eq!(parse_amount("2."), Ok(2_000_000_000));
+        assert!(parse_amount("0.0000000001").is_err());
+        assert!(parse_amount("-1").is_err());
+        assert!(parse_amount("1e9").is_err());
+        assert!(parse_amount(".").is_err());
+        assert!(parse_amount("18446744074").is_err());
+    }
+
+    #[test]
+    fn formats_without_trailing_zeros() {
+        assert_eq!(format_amount(0), "0");
+        assert_eq!(format_amount(1_500_000_000), "1.5");
+        assert_eq!(format_amount(1), "0.000000001");
+        assert_eq!(format_price(27_958_993_476), "0.000000027958993476");
+        for amount in ["0.25", "1073000000", "0.000000001"] {
+            assert_eq!(format_amount(parse_amount(amount).unwrap()), amount);
+        }
+    }
+}
@@ -0,0 +1,285 @@
+//! Read-only commands: dump accounts and list tokens.
+
+use std::fmt::Display;
+
+use anchor_lang::{Discriminator, Space};
+use anyhow::{Context, Error, Result};
+use audio_token_platform_client::program::{
+    CreatorFeeVault, LpAccount, LpPosition, PlatformConfig, TokenConfig,
+};
+use audio_token_platform_client::{accounts, pda, quote, ID};
+use clap::Args;
+use solana_account_decoder::UiAccountEncoding;
+use solana_rpc_client_api::config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_rpc_client_api::filter::{Memcmp, RpcFilterType};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::amount::{format_amount, format_price};
+use crate::rpc::Rpc;
+
+/// Offset of `TokenConfig::creator`: discriminator, then `mint`.
+const CREATOR_OFFSET: usize = 8 + 32;
+
+/// Most accounts `getMultipleAccounts` returns per request.
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+#[derive(Args)]
+pub struct ListArgs {
+    /// Only tokens created by this address
+    #[arg(long)]
+    creator: Option<Pubkey>,
+    /// Include graduated tokens
+    #[arg(long)]
+    all: bool,
+}
+
+fn field(label: &str, value: impl Display) {
+    println!("  {label:<24} {value}");
+}
+
+fn flag(value: bool) -> &'static str {
+    if value {
+        "yes"
+    } else {
+        "no"
+    }
+}
+
+fn status(token_config: &TokenConfig, lp_account: &LpAccount) -> Result<&'static str> {
+    Ok(if token_config.graduated {
+        "graduated"
+    } else if token_config.is_curve_complete(lp_account)? {
+        "awaiting graduation"
+    } else if token_config.trading_halted {
+        "halted"
+    } else {
+        "trading"
+    })
+}
+
+/// The curve's own SOL, excluding LP deposits, as a percentage of the
+/// graduation threshold.
+fn graduation_progress(token_config: &TokenConfig, lp_account: &LpAccount) -> Result<String> {
+    let percent = token_config.curve_sol_reserves(lp_account)? as f64 * 100.0
+        / token_config.graduation_threshold.max(1) as f64;
+    Ok(format!("{:.1}%", percent.min(100.0)))
+}
+
+pub fn platform(rpc: &Rpc) -> Result<()> {
+    let address = pda::platform_config_address();
+    let config: PlatformConfig = rpc.fetch(&address)?;
+
+    println!("Platform config {address}");
+    field("admin", config.admin);
+    field(
+        "pending admin",
+        config
+            .pending_admin
+            .map_or("none".to_string(), |admin| admin.to_string()),
+    );
+    field("fee recipient", config.fee_recipient);
+    field("trade fee", format!("{} bps", config.trade_fee_bps));
+    field("LP fee share", format!("{} bps", config.lp_fee_share_bps));
+    field(
+        "creator fee share",
+        format!("{} bps", config.creator_fee_share_bps),
+    );
+    field(
+        "max creator fee",
+        format!("{} bps", config.max_creator_fee_bps),
+    );
+    field(
+        "creation fee",
+        format!("{} SOL", format_amount(config.creation_fee)),
+    );
+    field(
+        "graduation threshold",
+        format!("{} SOL", format_amount(config.graduation_threshold)),
+    );
+    field("trading paused", flag(config.trading_paused));
+    field("creation paused", flag(config.creation_paused));
+    Ok(())
+}
+
+pub fn token(rpc: &Rpc, mint: &Pubkey) -> Result<()> {
+    let address = pda::token_config_address(mint);
+    let config: TokenConfig = rpc.fetch(&address)?;
+    let curve = config.curve();
+    let fee_vault: CreatorFeeVault = rpc.fetch(&pda::creator_fee_vault_address(mint))?;
+    let lp_account: LpAccount = rpc.fetch(&pda::lp_account_address(mint))?;
+
+    println!("Token config {address}");
+    field("mint", config.mint);
+    field("creator", config.creator);
+    field("name", &config.name);
+    field("symbol", &config.symbol);
+    field("metadata URI", &config.metadata_uri);
+    field("status", status(&config, &lp_account)?);
+    field("total supply", format_amount(config.total_supply));
+    field("curve", format!("{:?}", config.curve_type));
+    field(
+        "virtual SOL reserves",
+        format_amount(config.virtual_sol_reserves),
+    );
+    field(
+        "virtual token reserves",
+        format_amount(config.virtual_token_reserves),
+    );
+    field("real SOL reserves", format_amount(config.real_sol_reserves));
+    field(
+        "real token reserves",
+        format_amount(config.real_token_reserves),
+    );
+    field("tokens sold", format_amount(config.tokens_sold));
+    field(
+        "spot price",
+        format!(
+            "{} SOL",
+            format_price(quote::spot_price(&curve).map_err(Error::msg)?)
+        ),
+    );
+    field(
+        "market cap",
+        format!(
+            "{} SOL",
+            format_amount(quote::market_cap(&curve, config.total_supply).map_err(Error::msg)?)
+        ),
+    );
+    field(
+        "graduation",
+        format!(
+            "{} of {} SOL",
+            graduation_progress(&config, &lp_account)?,
+            format_amount(config.graduation_threshold)
+        ),
+    );
+    field(
+        "volume",
+        format!("{} SOL", format_amount(config.total_volume)),
+    );
+    field("creator fee", format!("{} bps", config.creator_fee_bps));
+    field(
+        "claimable creator fees",
+        format!(
+            "{} SOL",
+            format_amount(quote::claimable_creator_fees(&fee_vault))
+        ),
+    );
+    field("metadata locked", flag(config.metadata_locked));
+    field("created at", config.created_at);
+    Ok(())
+}
+
+pub fn lp(rpc: &Rpc, mint: &Pubkey, provider: Option<&Pubkey>) -> Result<()> {
+    let address = pda::lp_account_address(mint);
+    let lp_account: LpAccount = rpc.fetch(&address)?;
+
+    println!("LP account {address}");
+    field("mint", lp_account.mint);
+    field("liquidity", lp_account.liquidity);
+    field("locked liquidity", lp_account.locked_liquidity);
+    field("provider liquidity", lp_account.provider_liquidity());
+    field("fee per share", lp_account.fee_per_share);
+    field("updated at", lp_account.timestamp);
+
+    let Some(provider) = provider else {
+        return Ok(());
+    };
+    let address = pda::lp_position_address(mint, provider);
+    println!();
+    let Some(position) = rpc.fetch_optional::<LpPosition>(&address)? else {
+        println!("No LP position for {provider}");
+        return Ok(());
+    };
+    println!("LP position {address}");
+    field("provider", position.provider);
+    field("shares", position.shares);
+    field(
+        "claimable fees",
+        format!(
+            "{} SOL",
+            format_amount(quote::claimable_lp_fees(&lp_account, &position)?)
+        ),
+    );
+    Ok(())
+}
+
+pub fn list(rpc: &Rpc, args: &ListArgs) -> Result<()> {
+    let mut filters = vec![
+        RpcFilterType::DataSize((8 + TokenConfig::INIT_SPACE) as u64),
+        RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            0,
+            TokenConfig::DISCRIMINATOR.to_vec(),
+        )),
+    ];
+    if let Some(creator) = &args.creator {
+        filters.push(RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            CREATOR_OFFSET,
+            creator.to_bytes().to_vec(),
+        )));
+    }
+    let config = RpcProgramAccountsConfig {
+        filters: Some(filters),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+    let fetched = rpc
+        .client
+        .get_program_accounts_with_config(&ID, config)
+        .context("fetching token configs")?;
+
+    let mut tokens = fetched
+        .iter()
+        .map(|(address, account)| {
+            accounts::token_config(&account.data)
+                .with_context(|| format!("deserializing {address}"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    tokens.retain(|token| args.all || !token.graduated);
+    tokens.sort_by_key(|token| std::cmp::Reverse(token.created_at));
+    let lp_accounts = lp_accounts(rpc, &tokens)?;
+
+    println!(
+        "{:<44}  {:<10}  {:<20}  {:>14}  {:>6}  STATUS",
+        "MINT", "SYMBOL", "NAME", "REAL SOL", "GRAD"
+    );
+    for (token, lp_account) in tokens.iter().zip(&lp_accounts) {
+        println!(
+            "{:<44}  {:<10}  {:<20}  {:>14}  {:>6}  {}",
+            token.mint.to_string(),
+            token.symbol,
+            token.name.chars().take(20).collect::<String>(),
+            format_amount(token.real_sol_reserves),
+            graduation_progress(token, lp_account)?,
+            status(token, lp_account)?
+        );
+    }
+    println!("{} token(s)", tokens.len());
+    Ok(())
+}
+
+/// The `LpAccount` of each token, fetched in batches.
+fn lp_accounts(rpc: &Rpc, tokens: &[TokenConfig]) -> Result<Vec<LpAccount>> {
+    let addresses: Vec<Pubkey> = tokens
+        .iter()
+        .map(|token| pda::lp_account_address(&token.mint))
+        .collect();
+    let mut lp_accounts = Vec::with_capacity(addresses.len());
+    for batch in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        let fetched = rpc
+            .client
+            .get_multiple_accounts(batch)
+            .context("fetching LP accounts")?;
+        for (address, account) in batch.iter().zip(fetched) {
+            let account = account.with_context(|| format!("account {address} not found"))?;
+            lp_accounts.push(
+                accounts::lp_account(&account.data)
+                    .with_context(|| format!("deserializing {address}"))?,
+            );
+        }
+    }
+    Ok(lp_accounts)
+}
@@ -0,0 +1,131 @@
+//! `noiz-cli`: inspect and operate the Audio Token Platform program.
+//!
+//! Amounts of SOL and tokens are decimal (`0.5`), LP shares are raw. Point
+//! `--url` at `solana-test-validator` (the default) or any cluster.
+
+mod admin;
+mod amount;
+mod inspect;
+mod rpc;
+mod trade;
+
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::rpc::Rpc;
+
+#[derive(Parser)]
+#[command(name = "noiz-cli", version, about)]
+struct Cli {
+    /// JSON RPC URL
+    #[arg(
+        short,
+        long,
+        global = true,
+        env = "NOIZ_RPC_URL",
+        default_value = "http://127.0.0.1:8899"
+    )]
+    url: String,
+    /// Keypair that signs and pays for transactions
+    #[arg(
+        short,
+        long,
+        global = true,
+        env = "NOIZ_KEYPAIR",
+        default_value = "~/.config/solana/id.json"
+    )]
+    keypair: String,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create a token on a bonding curve, with a fresh mint
+    Create(trade::CreateArgs),
+    /// Buy tokens from the curve with SOL
+    Buy(trade::BuyArgs),
+    /// Sell tokens to the curve for SOL
+    Sell(trade::SellArgs),
+    /// Deposit SOL and tokens into a curve's liquidity
+    AddLiquidity(trade::AddLiquidityArgs),
+    /// Redeem LP shares for SOL and tokens
+    RemoveLiquidity(trade::RemoveLiquidityArgs),
+    /// Show the platform config
+    Platform,
+    /// Show a token's config, price and fees
+    Token { mint: Pubkey },
+    /// Show a token's LP account, and a provider's position
+    Lp {
+        mint: Pubkey,
+        #[arg(long)]
+        provider: Option<Pubkey>,
+    },
+    /// List tokens, newest first
+    List(inspect::ListArgs),
+    /// Platform admin actions
+    #[command(subcommand)]
+    Admin(admin::AdminCommand),
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let rpc = Rpc::new(&cli.url, &cli.keypair);
+
+    match &cli.command {
+        Command::Create(args) => trade::create(&rpc, args),
+        Command::Buy(args) => trade::buy(&rpc, args),
+        Command::Sell(args) => trade::sell(&rpc, args),
+        Command::AddLiquidity(args) => trade::add_liquidity(&rpc, args),
+        Command::RemoveLiquidity(args) => trade::remove_liquidity(&rpc, args),
+        Command::Platform => inspect::platform(&rpc),
+        Command::Token { mint } => inspect::token(&rpc, mint),
+        Command::Lp { mint, provider } => inspect::lp(&rpc, mint, provider.as_ref()),
+        Command::List(args) => inspect::list(&rpc, args),
+        Command::Admin(command) => admin::run(&rpc, command),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn cli_definition_is_valid() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn parses_amounts_and_admin_flags() {
+        let mint = Pubkey::new_unique();
+        let cli = Cli::parse_from([
+            "noiz-cli",
+            "-u",
+            "http://localhost:8899",
+            "buy",
+            &mint.to_string(),
+            "0.5",
+        ]);
+        assert_eq!(cli.url, "http://localhost:8899");
+        assert!(matches!(cli.command, Command::Buy(_)));
+
+        let cli = Cli::parse_from([
+            "noiz-cli",
+            "admin",
+            "update",
+            "--trading-paused",
+            "true",
+            "--creation-fee",
+            "0.02",
+        ]);
+        let Command::Admin(admin::AdminCommand::Update(args)) = &cli.command else {
+            panic!("expected admin update");
+        };
+        let params = audio_token_platform_client::program::UpdatePlatformConfigParams::from(args);
+        assert_eq!(params.trading_paused, Some(true));
+        assert_eq!(params.creation_fee, Some(20_000_000));
+        assert_eq!(params.trade_fee_bps, None);
+    }
+}
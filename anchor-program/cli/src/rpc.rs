@@ -0,0 +1,102 @@
+//! RPC connection and transaction signing.
+
+use std::path::PathBuf;
+
+use anchor_lang::AccountDeserialize;
+use anyhow::{anyhow, Context, Result};
+use audio_token_platform_client::accounts;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_rpc_client_api::client_error::{Error as ClientError, ErrorKind as ClientErrorKind};
+use solana_rpc_client_api::request::{RpcError, RpcResponseErrorData};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signature, Signer};
+use solana_sdk::transaction::Transaction;
+
+pub struct Rpc {
+    pub client: RpcClient,
+    keypair_path: PathBuf,
+}
+
+impl Rpc {
+    pub fn new(url: &str, keypair_path: &str) -> Self {
+        Rpc {
+            client: RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed()),
+            keypair_path: expand_home(keypair_path),
+        }
+    }
+
+    /// The `--keypair`, loaded only by commands that sign.
+    pub fn payer(&self) -> Result<Keypair> {
+        read_keypair_file(&self.keypair_path)
+            .map_err(|error| anyhow!("reading keypair {}: {error}", self.keypair_path.display()))
+    }
+
+    /// Fetches and deserializes one of the program's accounts, or `None` if
+    /// it doesn't exist.
+    pub fn fetch_optional<T: AccountDeserialize>(&self, address: &Pubkey) -> Result<Option<T>> {
+        let account = self
+            .client
+            .get_account_with_commitment(address, self.client.commitment())
+            .with_context(|| format!("fetching {address}"))?
+            .value;
+        account
+            .map(|account| {
+                accounts::deserialize(&account.data)
+                    .with_context(|| format!("deserializing {address}"))
+            })
+            .transpose()
+    }
+
+    pub fn fetch<T: AccountDeserialize>(&self, address: &Pubkey) -> Result<T> {
+        self.fetch_optional(address)?
+            .ok_or_else(|| anyhow!("account {address} not found"))
+    }
+
+    /// Signs `instructions` with the payer and `signers` and waits for
+    /// confirmation. Program logs from a failed simulation are included in
+    /// the error.
+    pub fn send(
+        &self,
+        payer: &Keypair,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<Signature> {
+        let blockhash = self
+            .client
+            .get_latest_blockhash()
+            .context("fetching latest blockhash")?;
+        let mut all_signers = vec![payer];
+        all_signers.extend_from_slice(signers);
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+        self.client
+            .send_and_confirm_transaction(&transaction)
+            .map_err(with_logs)
+    }
+}
+
+fn with_logs(error: ClientError) -> anyhow::Error {
+    if let ClientErrorKind::RpcError(RpcError::RpcResponseError {
+        data: RpcResponseErrorData::SendTransactionPreflightFailure(result),
+        ..
+    }) = error.kind()
+    {
+        if let Some(logs) = &result.logs {
+            return anyhow!("{error}\n\nProgram logs:\n  {}", logs.join("\n  "));
+        }
+    }
+    error.into()
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), std::env::var_os("HOME")) {
+        (Some(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
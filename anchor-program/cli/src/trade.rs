@@ -0,0 +1,250 @@
+//! Commands that create tokens, trade on the curve and provide liquidity.
+
+use anyhow::{ensure, Result};
+use audio_token_platform_client::program::{
+    CurveParams, CurveType, PlatformConfig, TokenConfig, BASIS_POINTS_DIVISOR,
+};
+use audio_token_platform_client::{instructions, pda, quote};
+use clap::{Args, ValueEnum};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::amount::{format_amount, parse_amount};
+use crate::rpc::Rpc;
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Curve {
+    ConstantProduct,
+    Linear,
+    Exponential,
+}
+
+impl From<Curve> for CurveType {
+    fn from(curve: Curve) -> Self {
+        match curve {
+            Curve::ConstantProduct => CurveType::ConstantProduct,
+            Curve::Linear => CurveType::Linear,
+            Curve::Exponential => CurveType::Exponential,
+        }
+    }
+}
+
+#[derive(Args)]
+pub struct CreateArgs {
+    #[arg(long)]
+    name: String,
+    #[arg(long)]
+    symbol: String,
+    /// Off-chain metadata JSON
+    #[arg(long)]
+    uri: String,
+    /// Total supply in whole tokens
+    #[arg(long, value_parser = parse_amount, default_value = "1000000000")]
+    supply: u64,
+    #[arg(long, value_enum, default_value_t = Curve::ConstantProduct)]
+    curve: Curve,
+    /// Virtual SOL reserves; with --virtual-tokens sets the starting price
+    #[arg(long, value_parser = parse_amount)]
+    virtual_sol: u64,
+    /// Virtual token reserves, in whole tokens
+    #[arg(long, value_parser = parse_amount)]
+    virtual_tokens: u64,
+    /// Creator royalty on every trade
+    #[arg(long, default_value_t = 0)]
+    creator_fee_bps: u64,
+}
+
+#[derive(Args)]
+pub struct BuyArgs {
+    mint: Pubkey,
+    /// SOL to spend, fees included
+    #[arg(value_parser = parse_amount)]
+    sol: u64,
+    /// Accepted shortfall from the quoted token amount
+    #[arg(long, default_value_t = 100)]
+    slippage_bps: u64,
+}
+
+#[derive(Args)]
+pub struct SellArgs {
+    mint: Pubkey,
+    /// Tokens to sell
+    #[arg(value_parser = parse_amount)]
+    tokens: u64,
+    /// Accepted shortfall from the quoted SOL amount
+    #[arg(long, default_value_t = 100)]
+    slippage_bps: u64,
+}
+
+#[derive(Args)]
+pub struct AddLiquidityArgs {
+    mint: Pubkey,
+    /// Most SOL to deposit
+    #[arg(value_parser = parse_amount)]
+    max_sol: u64,
+    /// Most tokens to deposit
+    #[arg(value_parser = parse_amount)]
+    max_tokens: u64,
+    /// Fewest LP shares to accept
+    #[arg(long, default_value_t = 1)]
+    min_shares: u64,
+}
+
+#[derive(Args)]
+pub struct RemoveLiquidityArgs {
+    mint: Pubkey,
+    /// LP shares to redeem
+    shares: u64,
+    /// Least SOL to accept
+    #[arg(long, value_parser = parse_amount, default_value = "0")]
+    min_sol: u64,
+    /// Least tokens to accept
+    #[arg(long, value_parser = parse_amount, default_value = "0")]
+    min_tokens: u64,
+}
+
+/// `amount` less `slippage_bps`, rounded down.
+fn less_slippage(amount: u64, slippage_bps: u64) -> u64 {
+    let kept = BASIS_POINTS_DIVISOR.saturating_sub(slippage_bps);
+    (amount as u128 * kept as u128 / BASIS_POINTS_DIVISOR as u128) as u64
+}
+
+fn fetch_pool(rpc: &Rpc, mint: &Pubkey) -> Result<(PlatformConfig, TokenConfig)> {
+    let platform_config = rpc.fetch(&pda::platform_config_address())?;
+    let token_config = rpc.fetch(&pda::token_config_address(mint))?;
+    Ok((platform_config, token_config))
+}
+
+pub fn create(rpc: &Rpc, args: &CreateArgs) -> Result<()> {
+    let payer = rpc.payer()?;
+    let mint = Keypair::new();
+    let platform_config: PlatformConfig = rpc.fetch(&pda::platform_config_address())?;
+
+    let instruction = instructions::create_audio_token(
+        &payer.pubkey(),
+        &mint.pubkey(),
+        &platform_config.fee_recipient,
+        &args.name,
+        &args.symbol,
+        &args.uri,
+        args.supply,
+        CurveParams {
+            curve_type: args.curve.into(),
+            virtual_sol_reserves: args.virtual_sol,
+            virtual_token_reserves: args.virtual_tokens,
+        },
+        args.creator_fee_bps,
+    );
+    let signature = rpc.send(&payer, &[instruction], &[&mint])?;
+
+    println!("Created {} ({})", args.symbol, mint.pubkey());
+    println!("Signature {signature}");
+    Ok(())
+}
+
+pub fn buy(rpc: &Rpc, args: &BuyArgs) -> Result<()> {
+    ensure!(
+        args.slippage_bps <= BASIS_POINTS_DIVISOR,
+        "slippage above 100%"
+    );
+    let payer = rpc.payer()?;
+    let (platform_config, token_config) = fetch_pool(rpc, &args.mint)?;
+    let quote = quote::buy(&platform_config, &token_config, args.sol)?;
+    let min_tokens_out = less_slippage(quote.token_amount, args.slippage_bps);
+
+    let instruction = instructions::buy_tokens(
+        &payer.pubkey(),
+        &args.mint,
+        &platform_config.fee_recipient,
+        args.sol,
+        min_tokens_out,
+        None,
+    );
+    let signature = rpc.send(&payer, &[instruction], &[])?;
+
+    println!(
+        "Bought about {} {} for {} SOL (at least {})",
+        format_amount(quote.token_amount),
+        token_config.symbol,
+        format_amount(args.sol),
+        format_amount(min_tokens_out)
+    );
+    println!("Signature {signature}");
+    Ok(())
+}
+
+pub fn sell(rpc: &Rpc, args: &SellArgs) -> Result<()> {
+    ensure!(
+        args.slippage_bps <= BASIS_POINTS_DIVISOR,
+        "slippage above 100%"
+    );
+    let payer = rpc.payer()?;
+    let (platform_config, token_config) = fetch_pool(rpc, &args.mint)?;
+    let quote = quote::sell(&platform_config, &token_config, args.tokens)?;
+    let min_sol_out = less_slippage(quote.net_sol_amount(), args.slippage_bps);
+
+    let instruction = instructions::sell_tokens(
+        &payer.pubkey(),
+        &args.mint,
+        &platform_config.fee_recipient,
+        args.tokens,
+        min_sol_out,
+        None,
+    );
+    let signature = rpc.send(&payer, &[instruction], &[])?;
+
+    println!(
+        "Sold {} {} for about {} SOL (at least {})",
+        format_amount(args.tokens),
+        token_config.symbol,
+        format_amount(quote.net_sol_amount()),
+        format_amount(min_sol_out)
+    );
+    println!("Signature {signature}");
+    Ok(())
+}
+
+pub fn add_liquidity(rpc: &Rpc, args: &AddLiquidityArgs) -> Result<()> {
+    let payer = rpc.payer()?;
+    let instruction = instructions::add_liquidity(
+        &payer.pubkey(),
+        &args.mint,
+        args.max_sol,
+        args.max_tokens,
+        args.min_shares,
+    );
+    let signature = rpc.send(&payer, &[instruction], &[])?;
+
+    println!("Added liquidity to {}", args.mint);
+    println!("Signature {signature}");
+    Ok(())
+}
+
+pub fn remove_liquidity(rpc: &Rpc, args: &RemoveLiquidityArgs) -> Result<()> {
+    let payer = rpc.payer()?;
+    let instruction = instructions::remove_liquidity(
+        &payer.pubkey(),
+        &args.mint,
+        args.shares,
+        args.min_sol,
+        args.min_tokens,
+    );
+    let signature = rpc.send(&payer, &[instruction], &[])?;
+
+    println!("Removed {} LP shares from {}", args.shares, args.mint);
+    println!("Signature {signature}");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slippage_rounds_down() {
+        assert_eq!(less_slippage(1_000, 100), 990);
+        assert_eq!(less_slippage(999, 100), 989);
+        assert_eq!(less_slippage(u64::MAX, 0), u64::MAX);
+        assert_eq!(less_slippage(1_000, BASIS_POINTS_DIVISOR), 0);
+    }
+}
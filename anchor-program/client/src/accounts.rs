@@ -0,0 +1,74 @@
+//! Deserializers for fetched account data.
+//!
+//! Each checks the 8-byte Anchor discriminator, so passing data from the
+//! wrong account type fails with `AccountDiscriminatorMismatch`.
+
+use anchor_lang::prelude::*;
+use audio_token_platform::{
+    CreatorFeeVault, LpAccount, LpPosition, PlatformConfig, TokenConfig, VestingSchedule,
+};
+
+/// Deserializes any of the program's accounts from its raw `data`.
+pub fn deserialize<T: AccountDeserialize>(mut data: &[u8]) -> Result<T> {
+    T::try_deserialize(&mut data)
+}
+
+pub fn platform_config(data: &[u8]) -> Result<PlatformConfig> {
+    deserialize(data)
+}
+
+pub fn token_config(data: &[u8]) -> Result<TokenConfig> {
+    deserialize(data)
+}
+
+pub fn lp_account(data: &[u8]) -> Result<LpAccount> {
+    deserialize(data)
+}
+
+pub fn lp_position(data: &[u8]) -> Result<LpPosition> {
+    deserialize(data)
+}
+
+pub fn creator_fee_vault(data: &[u8]) -> Result<CreatorFeeVault> {
+    deserialize(data)
+}
+
+pub fn vesting_schedule(data: &[u8]) -> Result<VestingSchedule> {
+    deserialize(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::error::ErrorCode;
+
+    fn lp_account_data() -> Vec<u8> {
+        let account = LpAccount {
+            mint: Pubkey::new_unique(),
+            liquidity: 1_000,
+            locked_liquidity: 400,
+            fee_per_share: 7,
+            timestamp: 1_700_000_000,
+            bump: 254,
+        };
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn round_trips_account_data() {
+        let account = lp_account(&lp_account_data()).unwrap();
+        assert_eq!(account.liquidity, 1_000);
+        assert_eq!(account.provider_liquidity(), 600);
+        assert_eq!(account.fee_per_share, 7);
+    }
+
+    #[test]
+    fn rejects_another_account_type() {
+        let Err(error) = token_config(&lp_account_data()) else {
+            panic!("deserialized an LpAccount as a TokenConfig");
+        };
+        assert_eq!(error, ErrorCode::AccountDiscriminatorMismatch.into());
+    }
+}
@@ -0,0 +1,469 @@
+//! Instruction builders, one per program instruction.
+//!
+//! Builders derive every PDA and associated token account themselves; pass
+//! only the signers, the mint and the platform's fee recipient. Account
+//! order and data encoding come from the program's own Anchor-generated
+//! `accounts` and `instruction` types.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{instruction::Instruction, system_program, sysvar};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::{self, get_associated_token_address};
+use anchor_spl::metadata::mpl_token_metadata;
+use anchor_spl::token::spl_token;
+use audio_token_platform::{accounts, instruction, CurveParams, UpdatePlatformConfigParams, ID};
+
+use crate::pda::*;
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+// ============================================================================
+// ADMIN
+// ============================================================================
+
+/// `admin` must be the program's upgrade authority.
+pub fn initialize_platform(
+    payer: &Pubkey,
+    admin: &Pubkey,
+    trade_fee_bps: u64,
+    creation_fee: u64,
+    fee_recipient: &Pubkey,
+    graduation_threshold: u64,
+) -> Instruction {
+    build(
+        accounts::InitializePlatform {
+            platform_config: platform_config_address(),
+            program: ID,
+            program_data: program_data_address(),
+            admin: *admin,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::InitializePlatform {
+            trade_fee_bps,
+            creation_fee,
+            fee_recipient: *fee_recipient,
+            graduation_threshold,
+        },
+    )
+}
+
+pub fn update_platform_config(admin: &Pubkey, params: UpdatePlatformConfigParams) -> Instruction {
+    build(
+        accounts::UpdatePlatformConfig {
+            platform_config: platform_config_address(),
+            admin: *admin,
+        },
+        instruction::UpdatePlatformConfig { params },
+    )
+}
+
+pub fn propose_admin(admin: &Pubkey, new_admin: &Pubkey) -> Instruction {
+    build(
+        accounts::ProposeAdmin {
+            platform_config: platform_config_address(),
+            admin: *admin,
+        },
+        instruction::ProposeAdmin {
+            new_admin: *new_admin,
+        },
+    )
+}
+
+pub fn accept_admin(pending_admin: &Pubkey) -> Instruction {
+    build(
+        accounts::AcceptAdmin {
+            platform_config: platform_config_address(),
+            pending_admin: *pending_admin,
+        },
+        instruction::AcceptAdmin {},
+    )
+}
+
+pub fn set_trading_halted(admin: &Pubkey, mint: &Pubkey, halted: bool) -> Instruction {
+    build(
+        accounts::SetTradingHalted {
+            platform_config: platform_config_address(),
+            token_config: token_config_address(mint),
+            mint: *mint,
+            admin: *admin,
+        },
+        instruction::SetTradingHalted { halted },
+    )
+}
+
+// ============================================================================
+// TOKENS
+// ============================================================================
+
+/// `mint` is a fresh keypair that must also sign.
+#[allow(clippy::too_many_arguments)]
+pub fn create_audio_token(
+    creator: &Pubkey,
+    mint: &Pubkey,
+    platform_fee_account: &Pubkey,
+    name: &str,
+    symbol: &str,
+    metadata_uri: &str,
+    total_supply: u64,
+    curve_params: CurveParams,
+    creator_fee_bps: u64,
+) -> Instruction {
+    build(
+        accounts::CreateAudioToken {
+            token_config: token_config_address(mint),
+            lp_account: lp_account_address(mint),
+            mint: *mint,
+            reserve_token_account: reserve_address(mint),
+            creator_vault: creator_vault_address(mint),
+            locked_vault: locked_vault_address(mint),
+            vesting_schedule: vesting_schedule_address(mint),
+            creator_fee_vault: creator_fee_vault_address(mint),
+            metadata_account: metadata_address(mint),
+            creator: *creator,
+            token_metadata_program: mpl_token_metadata::ID,
+            platform_config: platform_config_address(),
+            platform_fee_account: *platform_fee_account,
+            system_program: system_program::ID,
+            token_program: spl_token::ID,
+            associated_token_program: associated_token::ID,
+            rent: sysvar::rent::ID,
+        },
+        instruction::CreateAudioToken {
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            metadata_uri: metadata_uri.to_string(),
+            total_supply,
+            curve_params,
+            creator_fee_bps,
+        },
+    )
+}
+
+fn update_token_metadata_accounts(
+    creator: &Pubkey,
+    mint: &Pubkey,
+) -> accounts::UpdateTokenMetadata {
+    accounts::UpdateTokenMetadata {
+        token_config: token_config_address(mint),
+        mint: *mint,
+        metadata_account: metadata_address(mint),
+        creator: *creator,
+        token_metadata_program: mpl_token_metadata::ID,
+    }
+}
+
+pub fn update_token_metadata(
+    creator: &Pubkey,
+    mint: &Pubkey,
+    name: &str,
+    symbol: &str,
+    metadata_uri: &str,
+) -> Instruction {
+    build(
+        update_token_metadata_accounts(creator, mint),
+        instruction::UpdateTokenMetadata {
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            metadata_uri: metadata_uri.to_string(),
+        },
+    )
+}
+
+pub fn lock_metadata(creator: &Pubkey, mint: &Pubkey) -> Instruction {
+    build(
+        update_token_metadata_accounts(creator, mint),
+        instruction::LockMetadata {},
+    )
+}
+
+// ============================================================================
+// TRADING
+// ============================================================================
+
+fn buy_accounts(
+    buyer: &Pubkey,
+    mint: &Pubkey,
+    platform_fee_account: &Pubkey,
+) -> accounts::BuyTokens {
+    accounts::BuyTokens {
+        token_config: token_config_address(mint),
+        lp_account: lp_account_address(mint),
+        mint: *mint,
+        reserve_token_account: reserve_address(mint),
+        buyer_token_account: get_associated_token_address(buyer, mint),
+        buyer: *buyer,
+        platform_config: platform_config_address(),
+        platform_fee_account: *platform_fee_account,
+        creator_fee_vault: creator_fee_vault_address(mint),
+        system_program: system_program::ID,
+        token_program: spl_token::ID,
+        associated_token_program: associated_token::ID,
+    }
+}
+
+fn sell_accounts(
+    seller: &Pubkey,
+    mint: &Pubkey,
+    platform_fee_account: &Pubkey,
+) -> accounts::SellTokens {
+    accounts::SellTokens {
+        token_config: token_config_address(mint),
+        lp_account: lp_account_address(mint),
+        mint: *mint,
+        reserve_token_account: reserve_address(mint),
+        seller_token_account: get_associated_token_address(seller, mint),
+        seller: *seller,
+        platform_config: platform_config_address(),
+        platform_fee_account: *platform_fee_account,
+        creator_fee_vault: creator_fee_vault_address(mint),
+        token_program: spl_token::ID,
+    }
+}
+
+pub fn buy_tokens(
+    buyer: &Pubkey,
+    mint: &Pubkey,
+    platform_fee_account: &Pubkey,
+    sol_amount: u64,
+    min_tokens_out: u64,
+    deadline: Option<i64>,
+) -> Instruction {
+    build(
+        buy_accounts(buyer, mint, platform_fee_account),
+        instruction::BuyTokens {
+            sol_amount,
+            min_tokens_out,
+            deadline,
+        },
+    )
+}
+
+pub fn buy_exact_tokens_out(
+    buyer: &Pubkey,
+    mint: &Pubkey,
+    platform_fee_account: &Pubkey,
+    token_amount: u64,
+    max_sol_in: u64,
+    deadline: Option<i64>,
+) -> Instruction {
+    build(
+        buy_accounts(buyer, mint, platform_fee_account),
+        instruction::BuyExactTokensOut {
+            token_amount,
+            max_sol_in,
+            deadline,
+        },
+    )
+}
+
+pub fn sell_tokens(
+    seller: &Pubkey,
+    mint: &Pubkey,
+    platform_fee_account: &Pubkey,
+    token_amount: u64,
+    min_sol_out: u64,
+    deadline: Option<i64>,
+) -> Instruction {
+    build(
+        sell_accounts(seller, mint, platform_fee_account),
+        instruction::SellTokens {
+            token_amount,
+            min_sol_out,
+            deadline,
+        },
+    )
+}
+
+pub fn sell_for_exact_sol(
+    seller: &Pubkey,
+    mint: &Pubkey,
+    platform_fee_account: &Pubkey,
+    sol_amount: u64,
+    max_tokens_in: u64,
+    deadline: Option<i64>,
+) -> Instruction {
+    build(
+        sell_accounts(seller, mint, platform_fee_account),
+        instruction::SellForExactSol {
+            sol_amount,
+            max_tokens_in,
+            deadline,
+        },
+    )
+}
+
+// ============================================================================
+// LIQUIDITY
+// ============================================================================
+
+pub fn add_liquidity(
+    provider: &Pubkey,
+    mint: &Pubkey,
+    max_sol_in: u64,
+    max_tokens_in: u64,
+    min_shares_out: u64,
+) -> Instruction {
+    build(
+        accounts::AddLiquidity {
+            token_config: token_config_address(mint),
+            lp_account: lp_account_address(mint),
+            lp_position: lp_position_address(mint, provider),
+            mint: *mint,
+            reserve_token_account: reserve_address(mint),
+            provider_token_account: get_associated_token_address(provider, mint),
+            lp_provider: *provider,
+            platform_config: platform_config_address(),
+            token_program: spl_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::AddLiquidity {
+            max_sol_in,
+            max_tokens_in,
+            min_shares_out,
+        },
+    )
+}
+
+pub fn remove_liquidity(
+    provider: &Pubkey,
+    mint: &Pubkey,
+    lp_share: u64,
+    min_sol_out: u64,
+    min_tokens_out: u64,
+) -> Instruction {
+    build(
+        accounts::RemoveLiquidity {
+            token_config: token_config_address(mint),
+            lp_account: lp_account_address(mint),
+            lp_position: lp_position_address(mint, provider),
+            mint: *mint,
+            reserve_token_account: reserve_address(mint),
+            provider_token_account: get_associated_token_address(provider, mint),
+            lp_provider: *provider,
+            platform_config: platform_config_address(),
+            token_program: spl_token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::RemoveLiquidity {
+            lp_share,
+            min_sol_out,
+            min_tokens_out,
+        },
+    )
+}
+
+pub fn claim_lp_fees(provider: &Pubkey, mint: &Pubkey) -> Instruction {
+    build(
+        accounts::ClaimLpFees {
+            lp_account: lp_account_address(mint),
+            lp_position: lp_position_address(mint, provider),
+            mint: *mint,
+            lp_provider: *provider,
+        },
+        instruction::ClaimLpFees {},
+    )
+}
+
+// ============================================================================
+// CREATOR AND GRADUATION
+// ============================================================================
+
+pub fn claim_creator_fees(creator: &Pubkey, mint: &Pubkey) -> Instruction {
+    build(
+        accounts::ClaimCreatorFees {
+            creator_fee_vault: creator_fee_vault_address(mint),
+            mint: *mint,
+            creator: *creator,
+        },
+        instruction::ClaimCreatorFees {},
+    )
+}
+
+pub fn claim_vested(beneficiary: &Pubkey, mint: &Pubkey) -> Instruction {
+    build(
+        accounts::ClaimVested {
+            vesting_schedule: vesting_schedule_address(mint),
+            token_config: token_config_address(mint),
+            mint: *mint,
+            creator_vault: creator_vault_address(mint),
+            beneficiary_token_account: get_associated_token_address(beneficiary, mint),
+            beneficiary: *beneficiary,
+            system_program: system_program::ID,
+            token_program: spl_token::ID,
+            associated_token_program: associated_token::ID,
+        },
+        instruction::ClaimVested {},
+    )
+}
+
+/// Permissionless once the curve is complete; `payer` funds the migration vault.
+pub fn graduate(payer: &Pubkey, mint: &Pubkey) -> Instruction {
+    let migration_vault = migration_vault_address(mint);
+    build(
+        accounts::Graduate {
+            token_config: token_config_address(mint),
+            lp_account: lp_account_address(mint),
+            mint: *mint,
+            reserve_token_account: reserve_address(mint),
+            locked_vault: locked_vault_address(mint),
+            migration_vault,
+            vault_token_account: get_associated_token_address(&migration_vault, mint),
+            payer: *payer,
+            system_program: system_program::ID,
+            token_program: spl_token::ID,
+            associated_token_program: associated_token::ID,
+        },
+        instruction::Graduate {},
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::Discriminator;
+
+    #[test]
+    fn buy_tokens_marks_only_the_buyer_as_signer() {
+        let (buyer, mint, fee_recipient) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let ix = buy_tokens(&buyer, &mint, &fee_recipient, 1_000, 1, Some(42));
+
+        assert_eq!(ix.program_id, ID);
+        assert_eq!(&ix.data[..8], instruction::BuyTokens::DISCRIMINATOR);
+        let signers: Vec<_> = ix.accounts.iter().filter(|meta| meta.is_signer).collect();
+        assert_eq!(signers.len(), 1);
+        assert_eq!(signers[0].pubkey, buyer);
+        assert!(ix
+            .accounts
+            .iter()
+            .any(|meta| meta.pubkey == reserve_address(&mint) && meta.is_writable));
+    }
+
+    #[test]
+    fn exact_variants_share_accounts_with_plain_trades() {
+        let (trader, mint, fee_recipient) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        assert_eq!(
+            buy_exact_tokens_out(&trader, &mint, &fee_recipient, 1, 2, None).accounts,
+            buy_tokens(&trader, &mint, &fee_recipient, 1, 2, None).accounts
+        );
+        assert_eq!(
+            sell_for_exact_sol(&trader, &mint, &fee_recipient, 1, 2, None).accounts,
+            sell_tokens(&trader, &mint, &fee_recipient, 1, 2, None).accounts
+        );
+    }
+}
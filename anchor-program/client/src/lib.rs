@@ -0,0 +1,17 @@
+//! Off-chain client for the Audio Token Platform program.
+//!
+//! - [`pda`]: addresses of the program's accounts
+//! - [`instructions`]: one builder per program instruction
+//! - [`accounts`]: deserializers for fetched account data
+//! - [`quote`]: trade quotes computed by the same code the program runs
+//!
+//! The program crate is re-exported as [`program`] for its account types,
+//! instruction params, constants and `ErrorCode`.
+
+pub mod accounts;
+pub mod instructions;
+pub mod pda;
+pub mod quote;
+
+pub use audio_token_platform as program;
+pub use audio_token_platform::ID;
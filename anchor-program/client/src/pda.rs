@@ -0,0 +1,97 @@
+//! Program-derived addresses.
+//!
+//! Every PDA except the platform config is keyed by the token's mint.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::metadata::mpl_token_metadata;
+use audio_token_platform::{
+    CREATOR_FEE_VAULT_SEED, CREATOR_VAULT_SEED, ID, LOCKED_VAULT_SEED, LP_ACCOUNT_SEED,
+    LP_POSITION_SEED, MIGRATION_VAULT_SEED, PLATFORM_CONFIG_SEED, TOKEN_CONFIG_SEED,
+    VESTING_SCHEDULE_SEED,
+};
+
+pub fn platform_config_address() -> Pubkey {
+    Pubkey::find_program_address(&[PLATFORM_CONFIG_SEED], &ID).0
+}
+
+/// The token's `TokenConfig`, which also holds the curve's SOL reserves.
+pub fn token_config_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[TOKEN_CONFIG_SEED, mint.as_ref()], &ID).0
+}
+
+pub fn lp_account_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[LP_ACCOUNT_SEED, mint.as_ref()], &ID).0
+}
+
+pub fn lp_position_address(mint: &Pubkey, provider: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[LP_POSITION_SEED, mint.as_ref(), provider.as_ref()], &ID).0
+}
+
+pub fn creator_vault_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[CREATOR_VAULT_SEED, mint.as_ref()], &ID).0
+}
+
+pub fn locked_vault_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[LOCKED_VAULT_SEED, mint.as_ref()], &ID).0
+}
+
+pub fn creator_fee_vault_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[CREATOR_FEE_VAULT_SEED, mint.as_ref()], &ID).0
+}
+
+pub fn vesting_schedule_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[VESTING_SCHEDULE_SEED, mint.as_ref()], &ID).0
+}
+
+pub fn migration_vault_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[MIGRATION_VAULT_SEED, mint.as_ref()], &ID).0
+}
+
+/// The curve's token reserve: the `TokenConfig` PDA's associated token account.
+pub fn reserve_address(mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(&token_config_address(mint), mint)
+}
+
+/// The Metaplex metadata account for `mint`.
+pub fn metadata_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"metadata", mpl_token_metadata::ID.as_ref(), mint.as_ref()],
+        &mpl_token_metadata::ID,
+    )
+    .0
+}
+
+/// The program's `ProgramData` account, whose upgrade authority may
+/// initialize the platform.
+pub fn program_data_address() -> Pubkey {
+    Pubkey::find_program_address(&[ID.as_ref()], &bpf_loader_upgradeable::ID).0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mint_keyed_addresses_differ_per_mint() {
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        assert_ne!(token_config_address(&a), token_config_address(&b));
+        assert_ne!(token_config_address(&a), lp_account_address(&a));
+        assert_ne!(lp_position_address(&a, &b), lp_position_address(&b, &a));
+    }
+
+    #[test]
+    fn addresses_are_off_curve() {
+        let mint = Pubkey::new_unique();
+        for address in [
+            platform_config_address(),
+            token_config_address(&mint),
+            lp_account_address(&mint),
+            creator_fee_vault_address(&mint),
+            migration_vault_address(&mint),
+        ] {
+            assert!(!address.is_on_curve());
+        }
+    }
+}
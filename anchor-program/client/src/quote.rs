@@ -0,0 +1,58 @@
+//! Quotes computed by the same code the program runs.
+//!
+//! Trade quotes are the program's own [`audio_token_platform::quote`]
+//! functions, which run the `audio_token_quote` crate, so a quote built from
+//! freshly fetched accounts matches what the instruction settles to the
+//! lamport. Use the quoted amounts, less a slippage tolerance, as the
+//! instruction's `min_*`/`max_*` bounds.
+
+use anchor_lang::prelude::*;
+use audio_token_platform::{CreatorFeeVault, LpAccount, LpPosition};
+
+pub use audio_token_platform::quote::{buy, buy_exact_tokens_out, pool, sell, sell_for_exact_sol};
+pub use audio_token_quote::{
+    market_cap, price_impact_bps, spot_price, trade_fees, Pool, QuoteError, Side, TradeQuote,
+};
+
+/// Lamports `claim_lp_fees` would pay `position` right now.
+pub fn claimable_lp_fees(lp_account: &LpAccount, position: &LpPosition) -> Result<u64> {
+    let mut position = position.clone();
+    position.settle_fees(lp_account.fee_per_share)?;
+    Ok(position.unclaimed_fees)
+}
+
+/// Lamports `claim_creator_fees` would pay the creator right now.
+pub fn claimable_creator_fees(vault: &CreatorFeeVault) -> u64 {
+    vault.accrued_fees - vault.claimed_fees
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use audio_token_platform::FEE_PER_SHARE_SCALE;
+
+    #[test]
+    fn claimable_lp_fees_include_unsettled_accruals() {
+        let mut lp_account = LpAccount {
+            mint: Pubkey::new_unique(),
+            liquidity: 1_000,
+            locked_liquidity: 500,
+            fee_per_share: 0,
+            timestamp: 0,
+            bump: 255,
+        };
+        let position = LpPosition {
+            mint: lp_account.mint,
+            provider: Pubkey::new_unique(),
+            shares: 250,
+            fee_debt: 0,
+            unclaimed_fees: 3,
+            bump: 255,
+        };
+        lp_account.accrue_fees(1_000).unwrap();
+
+        // Half of the provider liquidity earns half of the fee
+        assert_eq!(lp_account.fee_per_share, 2 * FEE_PER_SHARE_SCALE);
+        assert_eq!(claimable_lp_fees(&lp_account, &position).unwrap(), 503);
+    }
+}
@@ -0,0 +1,25 @@
+//! Bonding curve types stored on-chain.
+//!
+//! Pricing lives in the `audio_token_quote` crate, shared with off-chain
+//! clients; `CurveType` here is its Anchor-serialized counterpart.
+
+use anchor_lang::prelude::*;
+
+pub use audio_token_quote::Curve;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum CurveType {
+    ConstantProduct,
+    Linear,
+    Exponential,
+}
+
+impl From<CurveType> for audio_token_quote::CurveType {
+    fn from(curve_type: CurveType) -> Self {
+        match curve_type {
+            CurveType::ConstantProduct => audio_token_quote::CurveType::ConstantProduct,
+            CurveType::Linear => audio_token_quote::CurveType::Linear,
+            CurveType::Exponential => audio_token_quote::CurveType::Exponential,
+        }
+    }
+}
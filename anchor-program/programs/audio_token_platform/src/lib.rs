@@ -2,50 +2,235 @@ use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
     metadata::{
-        create_metadata_accounts_v3, mpl_token_metadata::types::DataV2, CreateMetadataAccountsV3,
-        Metadata as Metaplex,
+        create_metadata_accounts_v3, mpl_token_metadata::types::DataV2,
+        update_metadata_accounts_v2, CreateMetadataAccountsV3, Metadata as Metaplex,
+        UpdateMetadataAccountsV2,
+    },
+    token::{
+        self, spl_token::instruction::AuthorityType, Mint, MintTo, SetAuthority, Token,
+        TokenAccount, Transfer,
     },
-    token::{self, Mint, MintTo, Token, TokenAccount, Transfer},
 };
 
+pub mod curve;
+pub mod quote;
+pub mod validation;
+
+pub use curve::{Curve, CurveType};
+use audio_token_quote::math::{mul_div_ceil, mul_div_floor};
+use quote::TradeQuote;
+use validation::validate_metadata;
+
 declare_id!("9m8ApaLxscUk6VhsuN12imf6ZvuCqPt42uDJMA1eRe7Y");
 
 // Constants
+pub const PLATFORM_CONFIG_SEED: &[u8] = b"platform_config";
 pub const TOKEN_CONFIG_SEED: &[u8] = b"token_config";
 pub const LP_ACCOUNT_SEED: &[u8] = b"lp_account";
-pub const PLATFORM_FEE_BPS: u64 = 25; // 0.25%
+pub const LP_POSITION_SEED: &[u8] = b"lp_position";
+pub const MIGRATION_VAULT_SEED: &[u8] = b"migration_vault";
+pub const CREATOR_VAULT_SEED: &[u8] = b"creator_vault";
+pub const LOCKED_VAULT_SEED: &[u8] = b"locked_vault";
+pub const CREATOR_FEE_VAULT_SEED: &[u8] = b"creator_fee_vault";
+pub const VESTING_SCHEDULE_SEED: &[u8] = b"vesting_schedule";
 pub const BASIS_POINTS_DIVISOR: u64 = 10000;
+pub const MAX_TRADE_FEE_BPS: u64 = 1000; // 10%
+pub const MAX_CREATOR_FEE_BPS: u64 = 500; // 5%, upper bound for the platform max
 pub const MAX_NAME_LEN: usize = 32;
 pub const MAX_SYMBOL_LEN: usize = 10;
 pub const MAX_METADATA_URI_LEN: usize = 200;
 
 // Initial liquidity constants
-pub const INITIAL_SOL_RESERVE: u64 = 10_000_000; // 0.01 SOL in lamports
 pub const INITIAL_TOKEN_RESERVE_PERCENT: u64 = 10; // 10% of total supply
+pub const CREATOR_ALLOCATION_PERCENT: u64 = 5; // 5% of total supply, vested
+
+// Fixed-point scale of `LpAccount::fee_per_share`
+pub const FEE_PER_SHARE_SCALE: u128 = 1_000_000_000_000;
+
+// Creator allocation vesting
+pub const VESTING_CLIFF_SECONDS: i64 = 0;
+pub const VESTING_DURATION_SECONDS: i64 = 21 * 24 * 60 * 60; // 21 days
+pub const VESTING_CLAIM_INTERVAL_SECONDS: i64 = 2 * 24 * 60 * 60; // 2 days
 
 #[program]
 pub mod audio_token_platform {
     use super::*;
 
+    /// Initializes the global platform config. Only the program's upgrade
+    /// authority can call this, and it becomes the platform admin.
+    ///
+    /// # Arguments
+    /// * `trade_fee_bps` - Fee charged on every buy and sell (max 10%)
+    /// * `creation_fee` - Flat fee charged on token creation (in lamports)
+    /// * `fee_recipient` - Wallet that receives trade and creation fees
+    /// * `graduation_threshold` - SOL reserves (in lamports) at which new tokens graduate
+    pub fn initialize_platform(
+        ctx: Context<InitializePlatform>,
+        trade_fee_bps: u64,
+        creation_fee: u64,
+        fee_recipient: Pubkey,
+        graduation_threshold: u64,
+    ) -> Result<()> {
+        require!(trade_fee_bps <= MAX_TRADE_FEE_BPS, ErrorCode::InvalidFeeConfig);
+        require!(graduation_threshold > 0, ErrorCode::InvalidAmount);
+
+        let platform_config = &mut ctx.accounts.platform_config;
+        platform_config.admin = ctx.accounts.admin.key();
+        platform_config.pending_admin = None;
+        platform_config.fee_recipient = fee_recipient;
+        platform_config.trade_fee_bps = trade_fee_bps;
+        platform_config.lp_fee_share_bps = 0;
+        platform_config.creator_fee_share_bps = 0;
+        platform_config.max_creator_fee_bps = 0;
+        platform_config.creation_fee = creation_fee;
+        platform_config.graduation_threshold = graduation_threshold;
+        platform_config.trading_paused = false;
+        platform_config.creation_paused = false;
+        platform_config.bump = ctx.bumps.platform_config;
+
+        emit_platform_config(platform_config)?;
+
+        msg!("Platform initialized, admin: {}", platform_config.admin);
+
+        Ok(())
+    }
+
+    /// Updates the global platform config (admin only). Fields left as
+    /// `None` keep their current value.
+    pub fn update_platform_config(
+        ctx: Context<UpdatePlatformConfig>,
+        params: UpdatePlatformConfigParams,
+    ) -> Result<()> {
+        let platform_config = &mut ctx.accounts.platform_config;
+
+        if let Some(trade_fee_bps) = params.trade_fee_bps {
+            require!(trade_fee_bps <= MAX_TRADE_FEE_BPS, ErrorCode::InvalidFeeConfig);
+            platform_config.trade_fee_bps = trade_fee_bps;
+        }
+        if let Some(lp_fee_share_bps) = params.lp_fee_share_bps {
+            platform_config.lp_fee_share_bps = lp_fee_share_bps;
+        }
+        if let Some(creator_fee_share_bps) = params.creator_fee_share_bps {
+            platform_config.creator_fee_share_bps = creator_fee_share_bps;
+        }
+        require!(
+            platform_config.lp_fee_share_bps
+                .checked_add(platform_config.creator_fee_share_bps)
+                .is_some_and(|total| total <= BASIS_POINTS_DIVISOR),
+            ErrorCode::InvalidFeeConfig
+        );
+        if let Some(max_creator_fee_bps) = params.max_creator_fee_bps {
+            require!(max_creator_fee_bps <= MAX_CREATOR_FEE_BPS, ErrorCode::InvalidFeeConfig);
+            platform_config.max_creator_fee_bps = max_creator_fee_bps;
+        }
+        if let Some(creation_fee) = params.creation_fee {
+            platform_config.creation_fee = creation_fee;
+        }
+        if let Some(fee_recipient) = params.fee_recipient {
+            platform_config.fee_recipient = fee_recipient;
+        }
+        if let Some(graduation_threshold) = params.graduation_threshold {
+            require!(graduation_threshold > 0, ErrorCode::InvalidAmount);
+            platform_config.graduation_threshold = graduation_threshold;
+        }
+        if let Some(trading_paused) = params.trading_paused {
+            platform_config.trading_paused = trading_paused;
+        }
+        if let Some(creation_paused) = params.creation_paused {
+            platform_config.creation_paused = creation_paused;
+        }
+
+        emit_platform_config(platform_config)?;
+
+        msg!("Platform config updated");
+
+        Ok(())
+    }
+
+    /// Proposes `new_admin` as the next platform admin (admin only). The
+    /// transfer completes once `new_admin` calls `accept_admin`; a new
+    /// proposal replaces the pending one.
+    ///
+    /// # Arguments
+    /// * `new_admin` - Wallet, or PDA of a multisig or governance program, to hand control to
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+        ctx.accounts.platform_config.pending_admin = Some(new_admin);
+
+        emit!(AdminProposed {
+            admin: ctx.accounts.admin.key(),
+            pending_admin: new_admin,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Proposed new admin: {}", new_admin);
+
+        Ok(())
+    }
+
+    /// Completes an admin transfer. Must be signed by the pending admin.
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        let platform_config = &mut ctx.accounts.platform_config;
+        let previous_admin = platform_config.admin;
+        platform_config.admin = ctx.accounts.pending_admin.key();
+        platform_config.pending_admin = None;
+
+        emit!(AdminTransferred {
+            previous_admin,
+            new_admin: platform_config.admin,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Admin transferred to {}", platform_config.admin);
+
+        Ok(())
+    }
+
+    /// Halts or resumes trading and liquidity changes for a single token
+    /// (admin only).
+    pub fn set_trading_halted(ctx: Context<SetTradingHalted>, halted: bool) -> Result<()> {
+        ctx.accounts.token_config.trading_halted = halted;
+
+        emit!(TradingHaltChanged {
+            mint: ctx.accounts.mint.key(),
+            halted,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Trading halted for {}: {}", ctx.accounts.mint.key(), halted);
+
+        Ok(())
+    }
+
     /// Creates a new audio token with a bonding curve for trading
     /// 
     /// # Arguments
     /// * `name` - Token name (max 32 chars)
-    /// * `symbol` - Token symbol (max 10 chars) 
-    /// * `metadata_uri` - IPFS URI for token metadata (max 200 chars)
+    /// * `symbol` - Token symbol (max 10 ASCII letters or digits)
+    /// * `metadata_uri` - Metadata URI: ipfs://, ar:// or https:// (max 200 chars)
     /// * `total_supply` - Total token supply in smallest units (with 9 decimals)
+    /// * `curve_params` - Curve type and the virtual reserves that set the starting price
+    /// * `creator_fee_bps` - Creator royalty charged on every trade (max `platform_config.max_creator_fee_bps`)
     pub fn create_audio_token(
         ctx: Context<CreateAudioToken>,
         name: String,
         symbol: String,
         metadata_uri: String,
         total_supply: u64,
+        curve_params: CurveParams,
+        creator_fee_bps: u64,
     ) -> Result<()> {
+        require!(
+            !ctx.accounts.platform_config.creation_paused,
+            ErrorCode::CreationPaused
+        );
+
         // Validate inputs
-        require!(name.len() <= MAX_NAME_LEN, ErrorCode::InvalidInput);
-        require!(symbol.len() <= MAX_SYMBOL_LEN, ErrorCode::InvalidInput);
-        require!(metadata_uri.len() <= MAX_METADATA_URI_LEN, ErrorCode::InvalidInput);
+        validate_metadata(&name, &symbol, &metadata_uri)?;
         require!(total_supply > 0, ErrorCode::InvalidAmount);
+        require!(
+            creator_fee_bps <= ctx.accounts.platform_config.max_creator_fee_bps,
+            ErrorCode::InvalidFeeConfig
+        );
 
         let mint = ctx.accounts.mint.key();
         let creator = ctx.accounts.creator.key();
@@ -61,6 +246,19 @@ pub mod audio_token_platform {
             .checked_div(100)
             .ok_or(ErrorCode::MathOverflow)?;
 
+        curve_params.validate(initial_token_reserve, ctx.accounts.platform_config.graduation_threshold)?;
+
+        // The creator's allocation is capped; the rest of the supply is locked
+        let creator_allocation = total_supply
+            .checked_mul(CREATOR_ALLOCATION_PERCENT)
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_div(100)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let locked_allocation = total_supply
+            .checked_sub(initial_token_reserve)
+            .and_then(|rest| rest.checked_sub(creator_allocation))
+            .ok_or(ErrorCode::MathOverflow)?;
+
         // Initialize TokenConfig account
         let token_config = &mut ctx.accounts.token_config;
         token_config.mint = mint;
@@ -70,64 +268,137 @@ pub mod audio_token_platform {
         token_config.metadata_uri = metadata_uri.clone();
         token_config.total_supply = total_supply;
         token_config.initial_supply = initial_token_reserve;
-        token_config.sol_reserves = INITIAL_SOL_RESERVE;
-        token_config.token_reserves = initial_token_reserve;
+        token_config.curve_type = curve_params.curve_type;
+        token_config.initial_virtual_sol_reserves = curve_params.virtual_sol_reserves;
+        token_config.initial_virtual_token_reserves = curve_params.virtual_token_reserves;
+        token_config.virtual_sol_reserves = curve_params.virtual_sol_reserves;
+        token_config.real_sol_reserves = 0;
+        token_config.virtual_token_reserves = curve_params.virtual_token_reserves;
+        token_config.real_token_reserves = initial_token_reserve;
         token_config.tokens_sold = 0;
         token_config.total_volume = 0;
         token_config.created_at = Clock::get()?.unix_timestamp;
+        token_config.graduation_threshold = ctx.accounts.platform_config.graduation_threshold;
+        token_config.graduated = false;
+        token_config.creator_fee_bps = creator_fee_bps;
+        token_config.trading_halted = false;
+        token_config.metadata_locked = false;
         token_config.bump = token_config_bump;
 
+        // Trade royalties and the creator's share of trade fees collect here
+        let creator_fee_vault = &mut ctx.accounts.creator_fee_vault;
+        creator_fee_vault.mint = mint;
+        creator_fee_vault.creator = creator;
+        creator_fee_vault.accrued_fees = 0;
+        creator_fee_vault.claimed_fees = 0;
+        creator_fee_vault.bump = ctx.bumps.creator_fee_vault;
+
+        // Vest the creator allocation linearly from creation
+        let vesting_schedule = &mut ctx.accounts.vesting_schedule;
+        vesting_schedule.mint = mint;
+        vesting_schedule.beneficiary = creator;
+        vesting_schedule.start_time = ctx.accounts.token_config.created_at;
+        vesting_schedule.cliff_seconds = VESTING_CLIFF_SECONDS;
+        vesting_schedule.duration_seconds = VESTING_DURATION_SECONDS;
+        vesting_schedule.claim_interval_seconds = VESTING_CLAIM_INTERVAL_SECONDS;
+        vesting_schedule.total_amount = creator_allocation;
+        vesting_schedule.claimed_amount = 0;
+        vesting_schedule.last_claim_at = 0;
+        vesting_schedule.bump = ctx.bumps.vesting_schedule;
+
         // Initialize LP Account
         let lp_account = &mut ctx.accounts.lp_account;
         lp_account.mint = mint;
-        lp_account.liquidity = INITIAL_SOL_RESERVE;
+        lp_account.liquidity = curve_params.virtual_sol_reserves;
+        lp_account.locked_liquidity = curve_params.virtual_sol_reserves;
+        lp_account.fee_per_share = 0;
         lp_account.timestamp = Clock::get()?.unix_timestamp;
         lp_account.bump = lp_account_bump;
 
-        // Collect platform fee (0.02 SOL = 20_000_000 lamports)
-        let platform_fee: u64 = 20_000_000;
-        let transfer_ix = anchor_lang::solana_program::system_instruction::transfer(
-            &ctx.accounts.creator.key(),
-            &ctx.accounts.platform_fee_account.key(),
-            platform_fee,
-        );
-        anchor_lang::solana_program::program::invoke(
-            &transfer_ix,
-            &[
-                ctx.accounts.creator.to_account_info(),
-                ctx.accounts.platform_fee_account.to_account_info(),
-                ctx.accounts.system_program.to_account_info(),
-            ],
-        )?;
+        // Collect platform creation fee
+        let creation_fee = ctx.accounts.platform_config.creation_fee;
+        if creation_fee > 0 {
+            let transfer_ix = anchor_lang::solana_program::system_instruction::transfer(
+                &ctx.accounts.creator.key(),
+                &ctx.accounts.platform_fee_account.key(),
+                creation_fee,
+            );
+            anchor_lang::solana_program::program::invoke(
+                &transfer_ix,
+                &[
+                    ctx.accounts.creator.to_account_info(),
+                    ctx.accounts.platform_fee_account.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+            )?;
+        }
+
+        // Mint the full supply into program-controlled accounts
+        // token_config PDA is the mint authority (set in account init)
+        let seeds = &[
+            TOKEN_CONFIG_SEED,
+            mint.as_ref(),
+            &[token_config_bump],
+        ];
+        let signer = &[&seeds[..]];
 
-        // Mint initial token supply to the reserve account
-        // Creator is the mint authority (set in account init)
         token::mint_to(
-            CpiContext::new(
+            CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
                 MintTo {
                     mint: ctx.accounts.mint.to_account_info(),
                     to: ctx.accounts.reserve_token_account.to_account_info(),
-                    authority: ctx.accounts.creator.to_account_info(),
+                    authority: ctx.accounts.token_config.to_account_info(),
                 },
+                signer,
             ),
             initial_token_reserve,
         )?;
 
+        if creator_allocation > 0 {
+            token::mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    MintTo {
+                        mint: ctx.accounts.mint.to_account_info(),
+                        to: ctx.accounts.creator_vault.to_account_info(),
+                        authority: ctx.accounts.token_config.to_account_info(),
+                    },
+                    signer,
+                ),
+                creator_allocation,
+            )?;
+        }
+
+        if locked_allocation > 0 {
+            token::mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    MintTo {
+                        mint: ctx.accounts.mint.to_account_info(),
+                        to: ctx.accounts.locked_vault.to_account_info(),
+                        authority: ctx.accounts.token_config.to_account_info(),
+                    },
+                    signer,
+                ),
+                locked_allocation,
+            )?;
+        }
+
         // Create token metadata using Metaplex
         let metadata_accounts = CreateMetadataAccountsV3 {
             metadata: ctx.accounts.metadata_account.to_account_info(),
             mint: ctx.accounts.mint.to_account_info(),
-            mint_authority: ctx.accounts.creator.to_account_info(),
+            mint_authority: ctx.accounts.token_config.to_account_info(),
             payer: ctx.accounts.creator.to_account_info(),
-            update_authority: ctx.accounts.creator.to_account_info(),
+            update_authority: ctx.accounts.token_config.to_account_info(),
             system_program: ctx.accounts.system_program.to_account_info(),
             rent: ctx.accounts.rent.to_account_info(),
         };
 
         let data_v2 = DataV2 {
-            name: name,
-            symbol: symbol,
+            name,
+            symbol,
             uri: metadata_uri,
             seller_fee_basis_points: 0,
             creators: None,
@@ -136,9 +407,10 @@ pub mod audio_token_platform {
         };
 
         create_metadata_accounts_v3(
-            CpiContext::new(
+            CpiContext::new_with_signer(
                 ctx.accounts.token_metadata_program.to_account_info(),
                 metadata_accounts,
+                signer,
             ),
             data_v2,
             true,  // is_mutable
@@ -146,9 +418,108 @@ pub mod audio_token_platform {
             None,  // collection_details
         )?;
 
+        // Revoke the mint authority so total_supply is a hard cap
+        token::set_authority(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                SetAuthority {
+                    current_authority: ctx.accounts.token_config.to_account_info(),
+                    account_or_mint: ctx.accounts.mint.to_account_info(),
+                },
+                signer,
+            ),
+            AuthorityType::MintTokens,
+            None,
+        )?;
+
+        emit!(TokenCreated {
+            mint,
+            creator,
+            name: ctx.accounts.token_config.name.clone(),
+            symbol: ctx.accounts.token_config.symbol.clone(),
+            metadata_uri: ctx.accounts.token_config.metadata_uri.clone(),
+            total_supply,
+            curve_type: curve_params.curve_type,
+            virtual_sol_reserves: curve_params.virtual_sol_reserves,
+            virtual_token_reserves: curve_params.virtual_token_reserves,
+            real_token_reserves: initial_token_reserve,
+            creator_allocation,
+            locked_allocation,
+            creator_fee_bps,
+            timestamp: ctx.accounts.token_config.created_at,
+        });
+
         msg!("Audio token created: {}", mint);
-        msg!("Initial SOL reserves: {} lamports", INITIAL_SOL_RESERVE);
+        msg!("Initial virtual SOL reserves: {} lamports", curve_params.virtual_sol_reserves);
+        msg!("Initial virtual token reserves: {}", curve_params.virtual_token_reserves);
         msg!("Initial token reserves: {}", initial_token_reserve);
+        msg!("Creator allocation: {}", creator_allocation);
+        msg!("Locked allocation: {}", locked_allocation);
+
+        Ok(())
+    }
+
+    /// Updates the token's name, symbol and metadata URI in both the
+    /// Metaplex metadata and `TokenConfig` (creator only).
+    ///
+    /// # Arguments
+    /// * `name` - Token name (max 32 chars)
+    /// * `symbol` - Token symbol (max 10 ASCII letters or digits)
+    /// * `metadata_uri` - Metadata URI: ipfs://, ar:// or https:// (max 200 chars)
+    pub fn update_token_metadata(
+        ctx: Context<UpdateTokenMetadata>,
+        name: String,
+        symbol: String,
+        metadata_uri: String,
+    ) -> Result<()> {
+        require!(!ctx.accounts.token_config.metadata_locked, ErrorCode::MetadataLocked);
+
+        // Validate inputs
+        validate_metadata(&name, &symbol, &metadata_uri)?;
+
+        let data_v2 = DataV2 {
+            name: name.clone(),
+            symbol: symbol.clone(),
+            uri: metadata_uri.clone(),
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: None,
+            uses: None,
+        };
+        ctx.accounts.update_metaplex(Some(data_v2), None)?;
+
+        let token_config = &mut ctx.accounts.token_config;
+        token_config.name = name;
+        token_config.symbol = symbol;
+        token_config.metadata_uri = metadata_uri;
+
+        emit!(TokenMetadataUpdated {
+            mint: token_config.mint,
+            name: token_config.name.clone(),
+            symbol: token_config.symbol.clone(),
+            metadata_uri: token_config.metadata_uri.clone(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Metadata updated for {}", token_config.mint);
+
+        Ok(())
+    }
+
+    /// Makes the token's metadata permanently immutable, both in Metaplex
+    /// and for `update_token_metadata` (creator only).
+    pub fn lock_metadata(ctx: Context<UpdateTokenMetadata>) -> Result<()> {
+        require!(!ctx.accounts.token_config.metadata_locked, ErrorCode::MetadataLocked);
+
+        ctx.accounts.update_metaplex(None, Some(false))?;
+        ctx.accounts.token_config.metadata_locked = true;
+
+        emit!(TokenMetadataLocked {
+            mint: ctx.accounts.token_config.mint,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Metadata locked for {}", ctx.accounts.token_config.mint);
 
         Ok(())
     }
@@ -158,117 +529,54 @@ pub mod audio_token_platform {
     /// # Arguments
     /// * `sol_amount` - Amount of SOL to spend (in lamports)
     /// * `min_tokens_out` - Minimum tokens to receive (slippage protection)
+    /// * `deadline` - Optional unix timestamp after which the trade fails
     pub fn buy_tokens(
         ctx: Context<BuyTokens>,
         sol_amount: u64,
         min_tokens_out: u64,
+        deadline: Option<i64>,
     ) -> Result<()> {
-        require!(sol_amount > 0, ErrorCode::InvalidAmount);
+        ctx.accounts.check_tradable()?;
+        check_deadline(deadline)?;
 
-        // Read values before mutable borrow
-        let sol_reserves = ctx.accounts.token_config.sol_reserves;
-        let token_reserves = ctx.accounts.token_config.token_reserves;
-        let bump = ctx.accounts.token_config.bump;
-        let mint_key = ctx.accounts.mint.key();
-        let token_config_key = ctx.accounts.token_config.key();
-        
-        // Calculate tokens out using constant product formula: x * y = k
-        let k = sol_reserves
-            .checked_mul(token_reserves)
-            .ok_or(ErrorCode::MathOverflow)?;
-        
-        let new_sol_reserves = sol_reserves
-            .checked_add(sol_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
-        
-        let new_token_reserves = k
-            .checked_div(new_sol_reserves)
-            .ok_or(ErrorCode::MathOverflow)?;
-        
-        let tokens_out = token_reserves
-            .checked_sub(new_token_reserves)
-            .ok_or(ErrorCode::InsufficientLiquidity)?;
+        // Price the buy on the token's curve; only the amount left after
+        // the trade fee and royalty reaches the curve
+        let quote = quote::buy(&ctx.accounts.platform_config, &ctx.accounts.token_config, sol_amount)?;
+        let tokens_out = quote.token_amount;
 
-        require!(tokens_out > 0, ErrorCode::InvalidAmount);
         require!(tokens_out >= min_tokens_out, ErrorCode::SlippageExceeded);
-        require!(tokens_out <= token_reserves, ErrorCode::InsufficientLiquidity);
 
-        // Calculate platform fee (0.25% of SOL amount)
-        let platform_fee = sol_amount
-            .checked_mul(PLATFORM_FEE_BPS)
-            .ok_or(ErrorCode::MathOverflow)?
-            .checked_div(BASIS_POINTS_DIVISOR)
-            .ok_or(ErrorCode::MathOverflow)?;
+        ctx.accounts.settle(&quote)?;
 
-        let sol_to_curve = sol_amount
-            .checked_sub(platform_fee)
-            .ok_or(ErrorCode::MathOverflow)?;
+        msg!("Bought {} tokens for {} lamports", tokens_out, sol_amount);
 
-        // Transfer SOL to token_config PDA (bonding curve reserves)
-        let transfer_to_curve_ix = anchor_lang::solana_program::system_instruction::transfer(
-            &ctx.accounts.buyer.key(),
-            &token_config_key,
-            sol_to_curve,
-        );
-        anchor_lang::solana_program::program::invoke(
-            &transfer_to_curve_ix,
-            &[
-                ctx.accounts.buyer.to_account_info(),
-                ctx.accounts.token_config.to_account_info(),
-                ctx.accounts.system_program.to_account_info(),
-            ],
-        )?;
+        Ok(())
+    }
 
-        // Transfer platform fee
-        if platform_fee > 0 {
-            let transfer_fee_ix = anchor_lang::solana_program::system_instruction::transfer(
-                &ctx.accounts.buyer.key(),
-                &ctx.accounts.platform_fee_account.key(),
-                platform_fee,
-            );
-            anchor_lang::solana_program::program::invoke(
-                &transfer_fee_ix,
-                &[
-                    ctx.accounts.buyer.to_account_info(),
-                    ctx.accounts.platform_fee_account.to_account_info(),
-                    ctx.accounts.system_program.to_account_info(),
-                ],
-            )?;
-        }
+    /// Buy an exact amount of tokens from the bonding curve
+    /// 
+    /// # Arguments
+    /// * `token_amount` - Amount of tokens to receive
+    /// * `max_sol_in` - Maximum SOL to spend, fee included (slippage protection)
+    /// * `deadline` - Optional unix timestamp after which the trade fails
+    pub fn buy_exact_tokens_out(
+        ctx: Context<BuyTokens>,
+        token_amount: u64,
+        max_sol_in: u64,
+        deadline: Option<i64>,
+    ) -> Result<()> {
+        ctx.accounts.check_tradable()?;
+        check_deadline(deadline)?;
 
-        // Transfer tokens from reserve to buyer
-        let seeds = &[
-            TOKEN_CONFIG_SEED,
-            mint_key.as_ref(),
-            &[bump],
-        ];
-        let signer = &[&seeds[..]];
+        // Price the tokens on the token's curve, then gross up for the fees
+        let quote = quote::buy_exact_tokens_out(&ctx.accounts.platform_config, &ctx.accounts.token_config, token_amount)?;
+        let sol_amount = quote.sol_amount;
 
-        token::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.reserve_token_account.to_account_info(),
-                    to: ctx.accounts.buyer_token_account.to_account_info(),
-                    authority: ctx.accounts.token_config.to_account_info(),
-                },
-                signer,
-            ),
-            tokens_out,
-        )?;
+        require!(sol_amount <= max_sol_in, ErrorCode::SlippageExceeded);
 
-        // Update token config state (mutable borrow at the end)
-        let token_config = &mut ctx.accounts.token_config;
-        token_config.sol_reserves = new_sol_reserves;
-        token_config.token_reserves = new_token_reserves;
-        token_config.tokens_sold = token_config.tokens_sold
-            .checked_add(tokens_out)
-            .ok_or(ErrorCode::MathOverflow)?;
-        token_config.total_volume = token_config.total_volume
-            .checked_add(sol_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
+        ctx.accounts.settle(&quote)?;
 
-        msg!("Bought {} tokens for {} lamports", tokens_out, sol_amount);
+        msg!("Bought {} tokens for {} lamports", token_amount, sol_amount);
 
         Ok(())
     }
@@ -278,100 +586,110 @@ pub mod audio_token_platform {
     /// # Arguments
     /// * `token_amount` - Amount of tokens to sell
     /// * `min_sol_out` - Minimum SOL to receive (slippage protection)
+    /// * `deadline` - Optional unix timestamp after which the trade fails
     pub fn sell_tokens(
         ctx: Context<SellTokens>,
         token_amount: u64,
         min_sol_out: u64,
+        deadline: Option<i64>,
     ) -> Result<()> {
-        require!(token_amount > 0, ErrorCode::InvalidAmount);
+        ctx.accounts.check_tradable()?;
+        check_deadline(deadline)?;
 
-        // Read values before mutable borrow
-        let sol_reserves = ctx.accounts.token_config.sol_reserves;
-        let token_reserves = ctx.accounts.token_config.token_reserves;
-        
-        // Calculate SOL out using constant product formula: x * y = k
-        let k = sol_reserves
-            .checked_mul(token_reserves)
-            .ok_or(ErrorCode::MathOverflow)?;
-        
-        let new_token_reserves = token_reserves
-            .checked_add(token_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
-        
-        let new_sol_reserves = k
-            .checked_div(new_token_reserves)
-            .ok_or(ErrorCode::MathOverflow)?;
-        
-        let sol_out = sol_reserves
-            .checked_sub(new_sol_reserves)
-            .ok_or(ErrorCode::InsufficientLiquidity)?;
+        // Price the sell on the token's curve, net of the trade fee and royalty
+        let quote = quote::sell(&ctx.accounts.platform_config, &ctx.accounts.token_config, token_amount)?;
+        let sol_to_seller = quote.net_sol_amount();
 
-        require!(sol_out > 0, ErrorCode::InvalidAmount);
-        require!(sol_out <= sol_reserves, ErrorCode::InsufficientLiquidity);
+        require!(sol_to_seller >= min_sol_out, ErrorCode::SlippageExceeded);
 
-        // Calculate platform fee (0.25% of SOL out)
-        let platform_fee = sol_out
-            .checked_mul(PLATFORM_FEE_BPS)
-            .ok_or(ErrorCode::MathOverflow)?
-            .checked_div(BASIS_POINTS_DIVISOR)
-            .ok_or(ErrorCode::MathOverflow)?;
+        ctx.accounts.settle(&quote)?;
 
-        let sol_to_seller = sol_out
-            .checked_sub(platform_fee)
-            .ok_or(ErrorCode::MathOverflow)?;
+        msg!("Sold {} tokens for {} lamports", token_amount, sol_to_seller);
 
-        require!(sol_to_seller >= min_sol_out, ErrorCode::SlippageExceeded);
+        Ok(())
+    }
 
-        // Transfer tokens from seller to reserve
-        token::transfer(
-            CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.seller_token_account.to_account_info(),
-                    to: ctx.accounts.reserve_token_account.to_account_info(),
-                    authority: ctx.accounts.seller.to_account_info(),
-                },
-            ),
-            token_amount,
-        )?;
+    /// Sell tokens back to the bonding curve for an exact amount of SOL
+    /// 
+    /// # Arguments
+    /// * `sol_amount` - Amount of SOL to receive, after fees (in lamports)
+    /// * `max_tokens_in` - Maximum tokens to sell (slippage protection)
+    /// * `deadline` - Optional unix timestamp after which the trade fails
+    pub fn sell_for_exact_sol(
+        ctx: Context<SellTokens>,
+        sol_amount: u64,
+        max_tokens_in: u64,
+        deadline: Option<i64>,
+    ) -> Result<()> {
+        ctx.accounts.check_tradable()?;
+        check_deadline(deadline)?;
 
-        // Transfer SOL from token_config PDA to seller
-        let token_config_info = ctx.accounts.token_config.to_account_info();
-        **token_config_info.try_borrow_mut_lamports()? -= sol_to_seller;
-        **ctx.accounts.seller.try_borrow_mut_lamports()? += sol_to_seller;
+        // Gross up for the fees, then price the tokens on the token's curve
+        let quote = quote::sell_for_exact_sol(&ctx.accounts.platform_config, &ctx.accounts.token_config, sol_amount)?;
+        let token_amount = quote.token_amount;
 
-        // Transfer platform fee
-        if platform_fee > 0 {
-            **token_config_info.try_borrow_mut_lamports()? -= platform_fee;
-            **ctx.accounts.platform_fee_account.try_borrow_mut_lamports()? += platform_fee;
-        }
+        require!(token_amount <= max_tokens_in, ErrorCode::SlippageExceeded);
 
-        // Update token config state (mutable borrow at the end)
-        let token_config = &mut ctx.accounts.token_config;
-        token_config.sol_reserves = new_sol_reserves;
-        token_config.token_reserves = new_token_reserves;
-        token_config.tokens_sold = token_config.tokens_sold
-            .checked_sub(token_amount)
-            .unwrap_or(0);
-        token_config.total_volume = token_config.total_volume
-            .checked_add(sol_out)
-            .ok_or(ErrorCode::MathOverflow)?;
+        ctx.accounts.settle(&quote)?;
 
-        msg!("Sold {} tokens for {} lamports", token_amount, sol_to_seller);
+        msg!("Sold {} tokens for {} lamports", token_amount, sol_amount);
 
         Ok(())
     }
 
     /// Add liquidity to the bonding curve (for LPs)
+    ///
+    /// Deposits at the current reserve ratio: `max_sol_in` and `max_tokens_in`
+    /// are upper bounds, and only the side that limits the deposit is taken
+    /// in full. Mints LP shares to the provider's `LpPosition`. Constant
+    /// product curves only: linear and exponential curves price on
+    /// `tokens_sold`, so their reserves can't value a share.
+    ///
+    /// # Arguments
+    /// * `max_sol_in` - Maximum SOL to deposit (in lamports)
+    /// * `max_tokens_in` - Maximum tokens to deposit
+    /// * `min_shares_out` - Minimum LP shares to receive (slippage protection)
     pub fn add_liquidity(
         ctx: Context<AddLiquidity>,
-        sol_amount: u64,
-        token_amount: u64,
+        max_sol_in: u64,
+        max_tokens_in: u64,
+        min_shares_out: u64,
     ) -> Result<()> {
-        require!(sol_amount > 0 && token_amount > 0, ErrorCode::InvalidAmount);
+        check_trading_live(&ctx.accounts.platform_config, &ctx.accounts.token_config)?;
+        require!(!ctx.accounts.token_config.graduated, ErrorCode::CurveComplete);
+        require!(
+            ctx.accounts.token_config.curve_type == CurveType::ConstantProduct,
+            ErrorCode::LiquidityUnsupported
+        );
+        require!(max_sol_in > 0 && max_tokens_in > 0, ErrorCode::InvalidAmount);
 
-        // Get key before mutable borrow
+        // Read values before mutable borrow
         let token_config_key = ctx.accounts.token_config.key();
+        let liquidity = ctx.accounts.lp_account.liquidity;
+        let virtual_sol_reserves = ctx.accounts.token_config.virtual_sol_reserves;
+        let virtual_token_reserves = ctx.accounts.token_config.virtual_token_reserves;
+
+        // Match the constant product reserve ratio, rounding the matched side up
+        let tokens_for_max_sol = mul_div_ceil(max_sol_in, virtual_token_reserves, virtual_sol_reserves)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let (sol_amount, token_amount) = if tokens_for_max_sol <= max_tokens_in {
+            (max_sol_in, tokens_for_max_sol)
+        } else {
+            let sol_for_max_tokens = mul_div_ceil(max_tokens_in, virtual_sol_reserves, virtual_token_reserves)
+                .ok_or(ErrorCode::MathOverflow)?;
+            (sol_for_max_tokens, max_tokens_in)
+        };
+
+        // Shares for each side of the deposit (rounded down); the smaller wins
+        let sol_shares = mul_div_floor(liquidity, sol_amount, virtual_sol_reserves)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let token_shares = mul_div_floor(liquidity, token_amount, virtual_token_reserves)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let shares = sol_shares.min(token_shares);
+
+        // A deposit too small to mint a share at the current ratio
+        require!(shares > 0, ErrorCode::InvalidPriceRatio);
+        require!(shares >= min_shares_out, ErrorCode::SlippageExceeded);
 
         // Transfer SOL to curve
         let transfer_ix = anchor_lang::solana_program::system_instruction::transfer(
@@ -404,53 +722,102 @@ pub mod audio_token_platform {
         // Update reserves (mutable borrow at the end)
         let token_config = &mut ctx.accounts.token_config;
         let lp_account = &mut ctx.accounts.lp_account;
+        let lp_position = &mut ctx.accounts.lp_position;
 
-        token_config.sol_reserves = token_config.sol_reserves
+        token_config.virtual_sol_reserves = token_config.virtual_sol_reserves
+            .checked_add(sol_amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        token_config.real_sol_reserves = token_config.real_sol_reserves
             .checked_add(sol_amount)
             .ok_or(ErrorCode::MathOverflow)?;
-        token_config.token_reserves = token_config.token_reserves
+        token_config.virtual_token_reserves = token_config.virtual_token_reserves
+            .checked_add(token_amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        token_config.real_token_reserves = token_config.real_token_reserves
             .checked_add(token_amount)
             .ok_or(ErrorCode::MathOverflow)?;
 
-        // Update LP account
+        // Update LP account and the provider's position
         lp_account.liquidity = lp_account.liquidity
-            .checked_add(sol_amount)
+            .checked_add(shares)
             .ok_or(ErrorCode::MathOverflow)?;
         lp_account.timestamp = Clock::get()?.unix_timestamp;
 
+        lp_position.mint = token_config.mint;
+        lp_position.provider = ctx.accounts.lp_provider.key();
+        lp_position.settle_fees(lp_account.fee_per_share)?;
+        lp_position.shares = lp_position.shares
+            .checked_add(shares)
+            .ok_or(ErrorCode::MathOverflow)?;
+        lp_position.reset_fee_debt(lp_account.fee_per_share)?;
+        lp_position.bump = ctx.bumps.lp_position;
+
+        emit!(LiquidityAdded {
+            mint: token_config.mint,
+            provider: ctx.accounts.lp_provider.key(),
+            sol_amount,
+            token_amount,
+            shares,
+            position_shares: lp_position.shares,
+            liquidity: lp_account.liquidity,
+            virtual_sol_reserves: token_config.virtual_sol_reserves,
+            real_sol_reserves: token_config.real_sol_reserves,
+            virtual_token_reserves: token_config.virtual_token_reserves,
+            real_token_reserves: token_config.real_token_reserves,
+            timestamp: lp_account.timestamp,
+        });
+
+        assert_reserves_backed(&ctx.accounts.token_config)?;
+
         msg!("Added liquidity: {} SOL + {} tokens", sol_amount, token_amount);
 
         Ok(())
     }
 
     /// Remove liquidity from the bonding curve
+    ///
+    /// Burns `lp_share` shares from the provider's `LpPosition` and pays out
+    /// the matching share of the reserves. Still open after graduation.
+    ///
+    /// # Arguments
+    /// * `lp_share` - LP shares to burn
+    /// * `min_sol_out` - Minimum SOL to receive (slippage protection)
+    /// * `min_tokens_out` - Minimum tokens to receive (slippage protection)
     pub fn remove_liquidity(
         ctx: Context<RemoveLiquidity>,
         lp_share: u64,
+        min_sol_out: u64,
+        min_tokens_out: u64,
     ) -> Result<()> {
+        check_trading_live(&ctx.accounts.platform_config, &ctx.accounts.token_config)?;
         require!(lp_share > 0, ErrorCode::InvalidAmount);
 
         // Read values before mutable borrow
         let liquidity = ctx.accounts.lp_account.liquidity;
-        let sol_reserves = ctx.accounts.token_config.sol_reserves;
-        let token_reserves = ctx.accounts.token_config.token_reserves;
+        let virtual_sol_reserves = ctx.accounts.token_config.virtual_sol_reserves;
+        let real_sol_reserves = ctx.accounts.token_config.real_sol_reserves;
+        let virtual_token_reserves = ctx.accounts.token_config.virtual_token_reserves;
+        let real_token_reserves = ctx.accounts.token_config.real_token_reserves;
         let bump = ctx.accounts.token_config.bump;
         let mint_key = ctx.accounts.mint.key();
 
+        require!(
+            lp_share <= ctx.accounts.lp_position.shares,
+            ErrorCode::InsufficientLiquidity
+        );
         require!(lp_share <= liquidity, ErrorCode::InsufficientLiquidity);
 
-        // Calculate proportional share of reserves
-        let sol_share = sol_reserves
-            .checked_mul(lp_share)
-            .ok_or(ErrorCode::MathOverflow)?
-            .checked_div(liquidity)
+        // Calculate proportional share of reserves (rounded down)
+        let sol_share = mul_div_floor(virtual_sol_reserves, lp_share, liquidity)
             .ok_or(ErrorCode::MathOverflow)?;
-        let token_share = token_reserves
-            .checked_mul(lp_share)
-            .ok_or(ErrorCode::MathOverflow)?
-            .checked_div(liquidity)
+        let token_share = mul_div_floor(virtual_token_reserves, lp_share, liquidity)
             .ok_or(ErrorCode::MathOverflow)?;
 
+        require!(sol_share >= min_sol_out, ErrorCode::SlippageExceeded);
+        require!(token_share >= min_tokens_out, ErrorCode::SlippageExceeded);
+        require!(sol_share <= real_sol_reserves, ErrorCode::InsufficientLiquidity);
+        require!(token_share <= real_token_reserves, ErrorCode::InsufficientLiquidity);
+
         // Transfer SOL to provider
         let token_config_info = ctx.accounts.token_config.to_account_info();
         **token_config_info.try_borrow_mut_lamports()? -= sol_share;
@@ -480,30 +847,684 @@ pub mod audio_token_platform {
         // Update reserves (mutable borrow at the end)
         let token_config = &mut ctx.accounts.token_config;
         let lp_account = &mut ctx.accounts.lp_account;
+        let lp_position = &mut ctx.accounts.lp_position;
 
-        token_config.sol_reserves = token_config.sol_reserves
+        token_config.virtual_sol_reserves = token_config.virtual_sol_reserves
             .checked_sub(sol_share)
             .ok_or(ErrorCode::MathOverflow)?;
-        token_config.token_reserves = token_config.token_reserves
+        token_config.real_sol_reserves = token_config.real_sol_reserves
+            .checked_sub(sol_share)
+            .ok_or(ErrorCode::MathOverflow)?;
+        token_config.virtual_token_reserves = token_config.virtual_token_reserves
+            .checked_sub(token_share)
+            .ok_or(ErrorCode::MathOverflow)?;
+        token_config.real_token_reserves = token_config.real_token_reserves
             .checked_sub(token_share)
             .ok_or(ErrorCode::MathOverflow)?;
 
-        // Update LP account
+        // Update LP account and the provider's position
         lp_account.liquidity = lp_account.liquidity
             .checked_sub(lp_share)
             .ok_or(ErrorCode::MathOverflow)?;
         lp_account.timestamp = Clock::get()?.unix_timestamp;
 
+        lp_position.settle_fees(lp_account.fee_per_share)?;
+        lp_position.shares = lp_position.shares
+            .checked_sub(lp_share)
+            .ok_or(ErrorCode::InsufficientLiquidity)?;
+        lp_position.reset_fee_debt(lp_account.fee_per_share)?;
+
+        emit!(LiquidityRemoved {
+            mint: token_config.mint,
+            provider: ctx.accounts.lp_provider.key(),
+            lp_share,
+            sol_amount: sol_share,
+            token_amount: token_share,
+            position_shares: lp_position.shares,
+            liquidity: lp_account.liquidity,
+            virtual_sol_reserves: token_config.virtual_sol_reserves,
+            real_sol_reserves: token_config.real_sol_reserves,
+            virtual_token_reserves: token_config.virtual_token_reserves,
+            real_token_reserves: token_config.real_token_reserves,
+            timestamp: lp_account.timestamp,
+        });
+
+        assert_reserves_backed(&ctx.accounts.token_config)?;
+
         msg!("Removed liquidity: {} SOL + {} tokens", sol_share, token_share);
 
         Ok(())
     }
+
+    /// Pays out the LP fees accrued to the provider's position.
+    pub fn claim_lp_fees(ctx: Context<ClaimLpFees>) -> Result<()> {
+        let fee_per_share = ctx.accounts.lp_account.fee_per_share;
+        let lp_position = &mut ctx.accounts.lp_position;
+        lp_position.settle_fees(fee_per_share)?;
+
+        let amount = lp_position.unclaimed_fees;
+        require!(amount > 0, ErrorCode::NothingToClaim);
+        lp_position.unclaimed_fees = 0;
+
+        // Fees are held as lamports on the LP account PDA
+        **ctx.accounts.lp_account.to_account_info().try_borrow_mut_lamports()? -= amount;
+        **ctx.accounts.lp_provider.try_borrow_mut_lamports()? += amount;
+
+        emit!(LpFeesClaimed {
+            mint: ctx.accounts.mint.key(),
+            provider: ctx.accounts.lp_provider.key(),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Claimed {} lamports of LP fees", amount);
+
+        Ok(())
+    }
+
+    /// Pays the creator the royalties and fee share collected in the creator
+    /// fee vault since the last claim.
+    pub fn claim_creator_fees(ctx: Context<ClaimCreatorFees>) -> Result<()> {
+        let creator_fee_vault = &mut ctx.accounts.creator_fee_vault;
+        let amount = creator_fee_vault.accrued_fees
+            .checked_sub(creator_fee_vault.claimed_fees)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(amount > 0, ErrorCode::NothingToClaim);
+        creator_fee_vault.claimed_fees = creator_fee_vault.accrued_fees;
+        let claimed_fees = creator_fee_vault.claimed_fees;
+
+        **ctx.accounts.creator_fee_vault.to_account_info().try_borrow_mut_lamports()? -= amount;
+        **ctx.accounts.creator.try_borrow_mut_lamports()? += amount;
+
+        emit!(CreatorFeesClaimed {
+            mint: ctx.accounts.mint.key(),
+            creator: ctx.accounts.creator.key(),
+            amount,
+            claimed_fees,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Claimed {} lamports of creator fees", amount);
+
+        Ok(())
+    }
+
+    /// Releases the creator's vested allocation from the creator vault.
+    /// Tokens vest linearly over the schedule's duration once the cliff has
+    /// passed, and can be claimed at most once per claim interval. Nothing
+    /// can be claimed before the token graduates, so the creator can never
+    /// sell the allocation into the curve.
+    pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+        require!(ctx.accounts.token_config.graduated, ErrorCode::AllocationLocked);
+
+        let now = Clock::get()?.unix_timestamp;
+        let vesting_schedule = &ctx.accounts.vesting_schedule;
+
+        if vesting_schedule.last_claim_at > 0 {
+            let next_claim_at = vesting_schedule
+                .last_claim_at
+                .checked_add(vesting_schedule.claim_interval_seconds)
+                .ok_or(ErrorCode::MathOverflow)?;
+            require!(now >= next_claim_at, ErrorCode::ClaimIntervalNotElapsed);
+        }
+
+        let amount = vesting_schedule
+            .vested_amount(now)?
+            .checked_sub(vesting_schedule.claimed_amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(amount > 0, ErrorCode::NothingToClaim);
+
+        // Transfer vested tokens from the creator vault to the beneficiary
+        let mint_key = ctx.accounts.mint.key();
+        let seeds = &[
+            TOKEN_CONFIG_SEED,
+            mint_key.as_ref(),
+            &[ctx.accounts.token_config.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.creator_vault.to_account_info(),
+                    to: ctx.accounts.beneficiary_token_account.to_account_info(),
+                    authority: ctx.accounts.token_config.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+
+        // Update vesting state (mutable borrow at the end)
+        let vesting_schedule = &mut ctx.accounts.vesting_schedule;
+        vesting_schedule.claimed_amount = vesting_schedule.claimed_amount
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        vesting_schedule.last_claim_at = now;
+
+        emit!(VestedTokensClaimed {
+            mint: mint_key,
+            beneficiary: vesting_schedule.beneficiary,
+            amount,
+            claimed_amount: vesting_schedule.claimed_amount,
+            total_amount: vesting_schedule.total_amount,
+            timestamp: now,
+        });
+
+        msg!("Claimed {} vested tokens", amount);
+
+        Ok(())
+    }
+
+    /// Graduates a token once the curve's own SOL reaches the graduation threshold.
+    /// Permissionless: anyone can call it. Trading on the curve stops and the
+    /// curve's reserves and the locked allocation move into the program-owned
+    /// migration vault as the DEX pool's liquidity; what liquidity providers
+    /// are owed stays behind for `remove_liquidity`.
+    /// No instruction releases the vault yet: it stays locked until the DEX
+    /// pool can be created from it by CPI.
+    pub fn graduate(ctx: Context<Graduate>) -> Result<()> {
+        require!(!ctx.accounts.token_config.graduated, ErrorCode::CurveComplete);
+        require!(
+            ctx.accounts.token_config.curve_sol_reserves(&ctx.accounts.lp_account)?
+                >= ctx.accounts.token_config.graduation_threshold,
+            ErrorCode::GraduationThresholdNotReached
+        );
+        assert_reserves_backed(&ctx.accounts.token_config)?;
+
+        // Read values before mutable borrow
+        let bump = ctx.accounts.token_config.bump;
+        let mint_key = ctx.accounts.mint.key();
+        let (lp_sol, lp_tokens) = ctx.accounts.token_config.lp_reserves(&ctx.accounts.lp_account)?;
+
+        // Everything but what LPs are owed migrates
+        let sol_amount = ctx.accounts.token_config.real_sol_reserves
+            .checked_sub(lp_sol)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let curve_tokens = ctx.accounts.token_config.real_token_reserves
+            .checked_sub(lp_tokens)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let locked_tokens = ctx.accounts.locked_vault.amount;
+        let token_amount = curve_tokens
+            .checked_add(locked_tokens)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        // Move SOL to the migration vault
+        let token_config_info = ctx.accounts.token_config.to_account_info();
+        **token_config_info.try_borrow_mut_lamports()? -= sol_amount;
+        **ctx.accounts.migration_vault.to_account_info().try_borrow_mut_lamports()? += sol_amount;
+
+        // Move reserve tokens to the migration vault
+        let seeds = &[
+            TOKEN_CONFIG_SEED,
+            mint_key.as_ref(),
+            &[bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.reserve_token_account.to_account_info(),
+                    to: ctx.accounts.vault_token_account.to_account_info(),
+                    authority: ctx.accounts.token_config.to_account_info(),
+                },
+                signer,
+            ),
+            curve_tokens,
+        )?;
+
+        // The locked allocation joins the curve's tokens as pool liquidity
+        if locked_tokens > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.locked_vault.to_account_info(),
+                        to: ctx.accounts.vault_token_account.to_account_info(),
+                        authority: ctx.accounts.token_config.to_account_info(),
+                    },
+                    signer,
+                ),
+                locked_tokens,
+            )?;
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+
+        let migration_vault = &mut ctx.accounts.migration_vault;
+        migration_vault.mint = mint_key;
+        migration_vault.sol_amount = sol_amount;
+        migration_vault.token_amount = token_amount;
+        migration_vault.graduated_at = now;
+        migration_vault.bump = ctx.bumps.migration_vault;
+
+        // The curve keeps only the LPs' reserves, which their shares now
+        // redeem one for one through `remove_liquidity`
+        let token_config = &mut ctx.accounts.token_config;
+        token_config.graduated = true;
+        token_config.virtual_sol_reserves = lp_sol;
+        token_config.real_sol_reserves = lp_sol;
+        token_config.virtual_token_reserves = lp_tokens;
+        token_config.real_token_reserves = lp_tokens;
+
+        let lp_account = &mut ctx.accounts.lp_account;
+        lp_account.liquidity = lp_account.provider_liquidity();
+        lp_account.locked_liquidity = 0;
+
+        emit!(TokenGraduated {
+            mint: mint_key,
+            sol_amount,
+            token_amount,
+            timestamp: now,
+        });
+
+        msg!("Token graduated: {}", mint_key);
+        msg!("Migrated {} lamports + {} tokens", sol_amount, token_amount);
+
+        Ok(())
+    }
+}
+
+// ============================================================================
+// HELPERS
+// ============================================================================
+
+/// Checks that the curve PDA holds enough lamports to stay rent exempt and
+/// pay out every lamport of `real_sol_reserves`. Run at the end of every
+/// instruction that moves curve SOL.
+fn assert_reserves_backed(token_config: &Account<TokenConfig>) -> Result<()> {
+    let info = token_config.to_account_info();
+    let required = Rent::get()?
+        .minimum_balance(info.data_len())
+        .checked_add(token_config.real_sol_reserves)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(info.lamports() >= required, ErrorCode::ReserveInvariantViolated);
+    Ok(())
+}
+
+/// Fails while trading is paused platform-wide or halted for this token.
+fn check_trading_live(platform_config: &PlatformConfig, token_config: &TokenConfig) -> Result<()> {
+    require!(!platform_config.trading_paused, ErrorCode::TradingPaused);
+    require!(!token_config.trading_halted, ErrorCode::TradingPaused);
+    Ok(())
+}
+
+/// Fails trades that land after the signer's `deadline`, if one was given.
+fn check_deadline(deadline: Option<i64>) -> Result<()> {
+    if let Some(deadline) = deadline {
+        require!(
+            Clock::get()?.unix_timestamp <= deadline,
+            ErrorCode::DeadlineExceeded
+        );
+    }
+    Ok(())
+}
+
+/// Emits the platform config's settings after initialization or an update.
+fn emit_platform_config(platform_config: &PlatformConfig) -> Result<()> {
+    emit!(PlatformConfigUpdated {
+        admin: platform_config.admin,
+        fee_recipient: platform_config.fee_recipient,
+        trade_fee_bps: platform_config.trade_fee_bps,
+        lp_fee_share_bps: platform_config.lp_fee_share_bps,
+        creator_fee_share_bps: platform_config.creator_fee_share_bps,
+        max_creator_fee_bps: platform_config.max_creator_fee_bps,
+        creation_fee: platform_config.creation_fee,
+        graduation_threshold: platform_config.graduation_threshold,
+        trading_paused: platform_config.trading_paused,
+        creation_paused: platform_config.creation_paused,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+impl<'info> UpdateTokenMetadata<'info> {
+    /// `update_metadata_accounts_v2` CPI signed by the `token_config` PDA,
+    /// which is the Metaplex update authority.
+    fn update_metaplex(&self, data: Option<DataV2>, is_mutable: Option<bool>) -> Result<()> {
+        let mint_key = self.mint.key();
+        let seeds = &[
+            TOKEN_CONFIG_SEED,
+            mint_key.as_ref(),
+            &[self.token_config.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        update_metadata_accounts_v2(
+            CpiContext::new_with_signer(
+                self.token_metadata_program.to_account_info(),
+                UpdateMetadataAccountsV2 {
+                    metadata: self.metadata_account.to_account_info(),
+                    update_authority: self.token_config.to_account_info(),
+                },
+                signer,
+            ),
+            None,  // new_update_authority
+            data,
+            None,  // primary_sale_happened
+            is_mutable,
+        )
+    }
+}
+
+impl<'info> BuyTokens<'info> {
+    fn check_tradable(&self) -> Result<()> {
+        check_trading_live(&self.platform_config, &self.token_config)?;
+        require!(!self.token_config.is_curve_complete(&self.lp_account)?, ErrorCode::CurveComplete);
+        Ok(())
+    }
+
+    /// System-program transfer from the buyer, skipped for zero amounts.
+    fn pay(&self, to: AccountInfo<'info>, lamports: u64) -> Result<()> {
+        if lamports == 0 {
+            return Ok(());
+        }
+        let transfer_ix = anchor_lang::solana_program::system_instruction::transfer(
+            &self.buyer.key(),
+            to.key,
+            lamports,
+        );
+        anchor_lang::solana_program::program::invoke(
+            &transfer_ix,
+            &[
+                self.buyer.to_account_info(),
+                to,
+                self.system_program.to_account_info(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Moves the quoted SOL (fees included) from the buyer and the quoted
+    /// tokens from the reserve, then updates the curve and emits `TokenBought`.
+    fn settle(&mut self, quote: &TradeQuote) -> Result<()> {
+        let TradeQuote { sol_amount, token_amount: tokens_out, trade_fee, creator_fee, .. } = *quote;
+        let sol_to_curve = trade_fee
+            .checked_add(creator_fee)
+            .and_then(|fees| sol_amount.checked_sub(fees))
+            .ok_or(ErrorCode::MathOverflow)?;
+        let real_token_reserves = self.token_config.real_token_reserves;
+        let bump = self.token_config.bump;
+        let mint_key = self.mint.key();
+
+        // Only tokens actually held by the curve can be bought
+        require!(tokens_out <= real_token_reserves, ErrorCode::InsufficientLiquidity);
+
+        // Split the fee between the platform, the creator and LPs
+        let fee_split = self.platform_config
+            .split_trade_fee(trade_fee, self.lp_account.provider_liquidity() > 0)?;
+
+        // Transfer SOL to token_config PDA (bonding curve reserves) and fees
+        self.pay(self.token_config.to_account_info(), sol_to_curve)?;
+        self.pay(self.platform_fee_account.to_account_info(), fee_split.platform)?;
+        let creator_total = fee_split.creator
+            .checked_add(creator_fee)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.pay(self.creator_fee_vault.to_account_info(), creator_total)?;
+        self.creator_fee_vault.accrue(creator_total)?;
+        self.pay(self.lp_account.to_account_info(), fee_split.lp)?;
+        self.lp_account.accrue_fees(fee_split.lp)?;
+
+        // Transfer tokens from reserve to buyer
+        let seeds = &[
+            TOKEN_CONFIG_SEED,
+            mint_key.as_ref(),
+            &[bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.reserve_token_account.to_account_info(),
+                    to: self.buyer_token_account.to_account_info(),
+                    authority: self.token_config.to_account_info(),
+                },
+                signer,
+            ),
+            tokens_out,
+        )?;
+
+        // Update token config state (mutable borrow at the end)
+        let token_config = &mut self.token_config;
+        token_config.virtual_sol_reserves = token_config.virtual_sol_reserves
+            .checked_add(sol_to_curve)
+            .ok_or(ErrorCode::MathOverflow)?;
+        token_config.real_sol_reserves = token_config.real_sol_reserves
+            .checked_add(sol_to_curve)
+            .ok_or(ErrorCode::MathOverflow)?;
+        token_config.virtual_token_reserves = token_config.virtual_token_reserves
+            .checked_sub(tokens_out)
+            .ok_or(ErrorCode::InsufficientLiquidity)?;
+        token_config.real_token_reserves = real_token_reserves
+            .checked_sub(tokens_out)
+            .ok_or(ErrorCode::InsufficientLiquidity)?;
+        token_config.tokens_sold = token_config.tokens_sold
+            .checked_add(tokens_out)
+            .ok_or(ErrorCode::MathOverflow)?;
+        token_config.total_volume = token_config.total_volume
+            .checked_add(sol_amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        emit!(TokenBought {
+            mint: mint_key,
+            trader: self.buyer.key(),
+            sol_amount,
+            token_amount: tokens_out,
+            fee: trade_fee,
+            creator_fee: fee_split.creator,
+            lp_fee: fee_split.lp,
+            creator_royalty: creator_fee,
+            virtual_sol_reserves: token_config.virtual_sol_reserves,
+            real_sol_reserves: token_config.real_sol_reserves,
+            virtual_token_reserves: token_config.virtual_token_reserves,
+            real_token_reserves: token_config.real_token_reserves,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        assert_reserves_backed(&self.token_config)?;
+
+        Ok(())
+    }
+}
+
+impl<'info> SellTokens<'info> {
+    fn check_tradable(&self) -> Result<()> {
+        check_trading_live(&self.platform_config, &self.token_config)?;
+        require!(!self.token_config.is_curve_complete(&self.lp_account)?, ErrorCode::CurveComplete);
+        Ok(())
+    }
+
+    /// Moves lamports out of the curve PDA, which the program owns.
+    fn pay_from_curve(&self, to: &AccountInfo<'info>, lamports: u64) -> Result<()> {
+        **self.token_config.to_account_info().try_borrow_mut_lamports()? -= lamports;
+        **to.try_borrow_mut_lamports()? += lamports;
+        Ok(())
+    }
+
+    /// Moves the quoted tokens from the seller and the quoted SOL (fees
+    /// included) from the curve, then updates the curve and emits `TokenSold`.
+    fn settle(&mut self, quote: &TradeQuote) -> Result<()> {
+        let TradeQuote { sol_amount: sol_out, token_amount, trade_fee, creator_fee, .. } = *quote;
+        let sol_to_seller = trade_fee
+            .checked_add(creator_fee)
+            .and_then(|fees| sol_out.checked_sub(fees))
+            .ok_or(ErrorCode::MathOverflow)?;
+        let real_sol_reserves = self.token_config.real_sol_reserves;
+        let real_token_reserves = self.token_config.real_token_reserves;
+
+        // Only SOL actually deposited into the curve can be paid out
+        require!(sol_out <= real_sol_reserves, ErrorCode::InsufficientLiquidity);
+
+        // Transfer tokens from seller to reserve
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.seller_token_account.to_account_info(),
+                    to: self.reserve_token_account.to_account_info(),
+                    authority: self.seller.to_account_info(),
+                },
+            ),
+            token_amount,
+        )?;
+
+        // Split the fee between the platform, the creator and LPs
+        let fee_split = self.platform_config
+            .split_trade_fee(trade_fee, self.lp_account.provider_liquidity() > 0)?;
+
+        // Transfer SOL and fees from token_config PDA
+        self.pay_from_curve(&self.seller.to_account_info(), sol_to_seller)?;
+        self.pay_from_curve(&self.platform_fee_account.to_account_info(), fee_split.platform)?;
+        let creator_total = fee_split.creator
+            .checked_add(creator_fee)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.pay_from_curve(&self.creator_fee_vault.to_account_info(), creator_total)?;
+        self.creator_fee_vault.accrue(creator_total)?;
+        self.pay_from_curve(&self.lp_account.to_account_info(), fee_split.lp)?;
+        self.lp_account.accrue_fees(fee_split.lp)?;
+
+        // Update token config state (mutable borrow at the end)
+        let token_config = &mut self.token_config;
+        token_config.virtual_sol_reserves = token_config.virtual_sol_reserves
+            .checked_sub(sol_out)
+            .ok_or(ErrorCode::InsufficientLiquidity)?;
+        token_config.real_sol_reserves = real_sol_reserves
+            .checked_sub(sol_out)
+            .ok_or(ErrorCode::InsufficientLiquidity)?;
+        token_config.virtual_token_reserves = token_config.virtual_token_reserves
+            .checked_add(token_amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        token_config.real_token_reserves = real_token_reserves
+            .checked_add(token_amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        token_config.tokens_sold = token_config.tokens_sold.saturating_sub(token_amount);
+        token_config.total_volume = token_config.total_volume
+            .checked_add(sol_out)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        emit!(TokenSold {
+            mint: token_config.mint,
+            trader: self.seller.key(),
+            sol_amount: sol_to_seller,
+            token_amount,
+            fee: trade_fee,
+            creator_fee: fee_split.creator,
+            lp_fee: fee_split.lp,
+            creator_royalty: creator_fee,
+            virtual_sol_reserves: token_config.virtual_sol_reserves,
+            real_sol_reserves: token_config.real_sol_reserves,
+            virtual_token_reserves: token_config.virtual_token_reserves,
+            real_token_reserves: token_config.real_token_reserves,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        assert_reserves_backed(&self.token_config)?;
+
+        Ok(())
+    }
 }
 
 // ============================================================================
 // ACCOUNT CONTEXTS
 // ============================================================================
 
+#[derive(Accounts)]
+pub struct InitializePlatform<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PlatformConfig::INIT_SPACE,
+        seeds = [PLATFORM_CONFIG_SEED],
+        bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ ErrorCode::Unauthorized
+    )]
+    pub program: Program<'info, crate::program::AudioTokenPlatform>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ ErrorCode::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePlatformConfig<'info> {
+    #[account(
+        mut,
+        seeds = [PLATFORM_CONFIG_SEED],
+        bump = platform_config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+// Admin accounts, including the upgrade authority in `InitializePlatform`,
+// are plain signers and never pay for anything, so the admin can be a PDA
+// that a multisig or governance program signs for via CPI.
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [PLATFORM_CONFIG_SEED],
+        bump = platform_config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [PLATFORM_CONFIG_SEED],
+        bump = platform_config.bump,
+        constraint = platform_config.pending_admin == Some(pending_admin.key()) @ ErrorCode::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub pending_admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetTradingHalted<'info> {
+    #[account(
+        seeds = [PLATFORM_CONFIG_SEED],
+        bump = platform_config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    pub mint: Account<'info, Mint>,
+
+    pub admin: Signer<'info>,
+}
+
 #[derive(Accounts)]
 #[instruction(name: String, symbol: String, metadata_uri: String, total_supply: u64)]
 pub struct CreateAudioToken<'info> {
@@ -529,7 +1550,7 @@ pub struct CreateAudioToken<'info> {
         init,
         payer = creator,
         mint::decimals = 9,
-        mint::authority = creator,
+        mint::authority = token_config,
     )]
     pub mint: Account<'info, Mint>,
 
@@ -541,6 +1562,47 @@ pub struct CreateAudioToken<'info> {
     )]
     pub reserve_token_account: Account<'info, TokenAccount>,
 
+    /// Holds the creator's share of the supply
+    #[account(
+        init,
+        payer = creator,
+        token::mint = mint,
+        token::authority = token_config,
+        seeds = [CREATOR_VAULT_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub creator_vault: Account<'info, TokenAccount>,
+
+    /// Holds the supply outside the curve and the creator allocation until
+    /// `graduate` moves it into the migration vault as pool liquidity
+    #[account(
+        init,
+        payer = creator,
+        token::mint = mint,
+        token::authority = token_config,
+        seeds = [LOCKED_VAULT_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub locked_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + VestingSchedule::INIT_SPACE,
+        seeds = [VESTING_SCHEDULE_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + CreatorFeeVault::INIT_SPACE,
+        seeds = [CREATOR_FEE_VAULT_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub creator_fee_vault: Account<'info, CreatorFeeVault>,
+
     /// CHECK: Metadata account created via CPI to Metaplex
     #[account(mut)]
     pub metadata_account: UncheckedAccount<'info>,
@@ -551,18 +1613,174 @@ pub struct CreateAudioToken<'info> {
     /// CHECK: Metaplex Token Metadata Program
     pub token_metadata_program: Program<'info, Metaplex>,
 
-    /// CHECK: Platform fee recipient
-    #[account(mut)]
+    #[account(
+        seeds = [PLATFORM_CONFIG_SEED],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// CHECK: Validated against the fee recipient stored in platform_config
+    #[account(
+        mut,
+        address = platform_config.fee_recipient @ ErrorCode::InvalidFeeRecipient
+    )]
+    pub platform_fee_account: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateTokenMetadata<'info> {
+    #[account(
+        mut,
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump,
+        has_one = creator @ ErrorCode::Unauthorized
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    pub mint: Account<'info, Mint>,
+
+    /// CHECK: Metaplex metadata PDA of `mint`, updated via CPI
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), mint.key().as_ref()],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub metadata_account: UncheckedAccount<'info>,
+
+    pub creator: Signer<'info>,
+
+    pub token_metadata_program: Program<'info, Metaplex>,
+}
+
+#[derive(Accounts)]
+pub struct BuyTokens<'info> {
+    #[account(
+        mut,
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [LP_ACCOUNT_SEED, mint.key().as_ref()],
+        bump = lp_account.bump
+    )]
+    pub lp_account: Account<'info, LpAccount>,
+
+    #[account(mut)]
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = token_config,
+    )]
+    pub reserve_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = mint,
+        associated_token::authority = buyer,
+    )]
+    pub buyer_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        seeds = [PLATFORM_CONFIG_SEED],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// CHECK: Validated against the fee recipient stored in platform_config
+    #[account(
+        mut,
+        address = platform_config.fee_recipient @ ErrorCode::InvalidFeeRecipient
+    )]
+    pub platform_fee_account: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [CREATOR_FEE_VAULT_SEED, mint.key().as_ref()],
+        bump = creator_fee_vault.bump
+    )]
+    pub creator_fee_vault: Account<'info, CreatorFeeVault>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct SellTokens<'info> {
+    #[account(
+        mut,
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [LP_ACCOUNT_SEED, mint.key().as_ref()],
+        bump = lp_account.bump
+    )]
+    pub lp_account: Account<'info, LpAccount>,
+
+    #[account(mut)]
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = token_config,
+    )]
+    pub reserve_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = seller,
+    )]
+    pub seller_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    #[account(
+        seeds = [PLATFORM_CONFIG_SEED],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// CHECK: Validated against the fee recipient stored in platform_config
+    #[account(
+        mut,
+        address = platform_config.fee_recipient @ ErrorCode::InvalidFeeRecipient
+    )]
     pub platform_fee_account: UncheckedAccount<'info>,
 
-    pub system_program: Program<'info, System>,
+    #[account(
+        mut,
+        seeds = [CREATOR_FEE_VAULT_SEED, mint.key().as_ref()],
+        bump = creator_fee_vault.bump
+    )]
+    pub creator_fee_vault: Account<'info, CreatorFeeVault>,
+
     pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
-pub struct BuyTokens<'info> {
+pub struct AddLiquidity<'info> {
     #[account(
         mut,
         seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
@@ -570,6 +1788,22 @@ pub struct BuyTokens<'info> {
     )]
     pub token_config: Account<'info, TokenConfig>,
 
+    #[account(
+        mut,
+        seeds = [LP_ACCOUNT_SEED, mint.key().as_ref()],
+        bump = lp_account.bump
+    )]
+    pub lp_account: Account<'info, LpAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = lp_provider,
+        space = 8 + LpPosition::INIT_SPACE,
+        seeds = [LP_POSITION_SEED, mint.key().as_ref(), lp_provider.key().as_ref()],
+        bump
+    )]
+    pub lp_position: Account<'info, LpPosition>,
+
     #[account(mut)]
     pub mint: Account<'info, Mint>,
 
@@ -581,27 +1815,27 @@ pub struct BuyTokens<'info> {
     pub reserve_token_account: Account<'info, TokenAccount>,
 
     #[account(
-        init_if_needed,
-        payer = buyer,
+        mut,
         associated_token::mint = mint,
-        associated_token::authority = buyer,
+        associated_token::authority = lp_provider,
     )]
-    pub buyer_token_account: Account<'info, TokenAccount>,
+    pub provider_token_account: Account<'info, TokenAccount>,
 
     #[account(mut)]
-    pub buyer: Signer<'info>,
+    pub lp_provider: Signer<'info>,
 
-    /// CHECK: Platform fee recipient
-    #[account(mut)]
-    pub platform_fee_account: UncheckedAccount<'info>,
+    #[account(
+        seeds = [PLATFORM_CONFIG_SEED],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
 
-    pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct SellTokens<'info> {
+pub struct RemoveLiquidity<'info> {
     #[account(
         mut,
         seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
@@ -610,11 +1844,19 @@ pub struct SellTokens<'info> {
     pub token_config: Account<'info, TokenConfig>,
 
     #[account(
+        mut,
         seeds = [LP_ACCOUNT_SEED, mint.key().as_ref()],
         bump = lp_account.bump
     )]
     pub lp_account: Account<'info, LpAccount>,
 
+    #[account(
+        mut,
+        seeds = [LP_POSITION_SEED, mint.key().as_ref(), lp_provider.key().as_ref()],
+        bump = lp_position.bump
+    )]
+    pub lp_position: Account<'info, LpPosition>,
+
     #[account(mut)]
     pub mint: Account<'info, Mint>,
 
@@ -626,24 +1868,29 @@ pub struct SellTokens<'info> {
     pub reserve_token_account: Account<'info, TokenAccount>,
 
     #[account(
-        mut,
+        init_if_needed,
+        payer = lp_provider,
         associated_token::mint = mint,
-        associated_token::authority = seller,
+        associated_token::authority = lp_provider,
     )]
-    pub seller_token_account: Account<'info, TokenAccount>,
+    pub provider_token_account: Account<'info, TokenAccount>,
 
     #[account(mut)]
-    pub seller: Signer<'info>,
+    pub lp_provider: Signer<'info>,
 
-    /// CHECK: Platform fee recipient
-    #[account(mut)]
-    pub platform_fee_account: UncheckedAccount<'info>,
+    #[account(
+        seeds = [PLATFORM_CONFIG_SEED],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
 
     pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct AddLiquidity<'info> {
+pub struct Graduate<'info> {
     #[account(
         mut,
         seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
@@ -658,7 +1905,6 @@ pub struct AddLiquidity<'info> {
     )]
     pub lp_account: Account<'info, LpAccount>,
 
-    #[account(mut)]
     pub mint: Account<'info, Mint>,
 
     #[account(
@@ -670,64 +1916,164 @@ pub struct AddLiquidity<'info> {
 
     #[account(
         mut,
+        seeds = [LOCKED_VAULT_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub locked_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + MigrationVault::INIT_SPACE,
+        seeds = [MIGRATION_VAULT_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub migration_vault: Account<'info, MigrationVault>,
+
+    #[account(
+        init,
+        payer = payer,
         associated_token::mint = mint,
-        associated_token::authority = lp_provider,
+        associated_token::authority = migration_vault,
     )]
-    pub provider_token_account: Account<'info, TokenAccount>,
+    pub vault_token_account: Account<'info, TokenAccount>,
 
     #[account(mut)]
-    pub lp_provider: Signer<'info>,
+    pub payer: Signer<'info>,
 
-    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
 #[derive(Accounts)]
-pub struct RemoveLiquidity<'info> {
+pub struct ClaimLpFees<'info> {
     #[account(
         mut,
-        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
-        bump = token_config.bump
+        seeds = [LP_ACCOUNT_SEED, mint.key().as_ref()],
+        bump = lp_account.bump
     )]
-    pub token_config: Account<'info, TokenConfig>,
+    pub lp_account: Account<'info, LpAccount>,
 
     #[account(
         mut,
-        seeds = [LP_ACCOUNT_SEED, mint.key().as_ref()],
-        bump = lp_account.bump
+        seeds = [LP_POSITION_SEED, mint.key().as_ref(), lp_provider.key().as_ref()],
+        bump = lp_position.bump
     )]
-    pub lp_account: Account<'info, LpAccount>,
+    pub lp_position: Account<'info, LpPosition>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub lp_provider: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimCreatorFees<'info> {
+    #[account(
+        mut,
+        seeds = [CREATOR_FEE_VAULT_SEED, mint.key().as_ref()],
+        bump = creator_fee_vault.bump,
+        has_one = creator @ ErrorCode::Unauthorized
+    )]
+    pub creator_fee_vault: Account<'info, CreatorFeeVault>,
+
+    pub mint: Account<'info, Mint>,
 
     #[account(mut)]
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    #[account(
+        mut,
+        seeds = [VESTING_SCHEDULE_SEED, mint.key().as_ref()],
+        bump = vesting_schedule.bump,
+        has_one = beneficiary @ ErrorCode::Unauthorized
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
     pub mint: Account<'info, Mint>,
 
     #[account(
         mut,
-        associated_token::mint = mint,
-        associated_token::authority = token_config,
+        seeds = [CREATOR_VAULT_SEED, mint.key().as_ref()],
+        bump
     )]
-    pub reserve_token_account: Account<'info, TokenAccount>,
+    pub creator_vault: Account<'info, TokenAccount>,
 
     #[account(
         init_if_needed,
-        payer = lp_provider,
+        payer = beneficiary,
         associated_token::mint = mint,
-        associated_token::authority = lp_provider,
+        associated_token::authority = beneficiary,
     )]
-    pub provider_token_account: Account<'info, TokenAccount>,
+    pub beneficiary_token_account: Account<'info, TokenAccount>,
 
     #[account(mut)]
-    pub lp_provider: Signer<'info>,
+    pub beneficiary: Signer<'info>,
 
+    pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
-    pub system_program: Program<'info, System>,
 }
 
 // ============================================================================
 // ACCOUNT STRUCTURES
 // ============================================================================
 
+#[account]
+#[derive(InitSpace)]
+pub struct PlatformConfig {
+    pub admin: Pubkey,             // 32 bytes
+    pub pending_admin: Option<Pubkey>, // 1 + 32 bytes
+    pub fee_recipient: Pubkey,     // 32 bytes
+    pub trade_fee_bps: u64,        // 8 bytes
+    pub lp_fee_share_bps: u64,     // 8 bytes
+    pub creator_fee_share_bps: u64, // 8 bytes
+    pub max_creator_fee_bps: u64,  // 8 bytes
+    pub creation_fee: u64,         // 8 bytes
+    pub graduation_threshold: u64, // 8 bytes
+    pub trading_paused: bool,      // 1 byte
+    pub creation_paused: bool,     // 1 byte
+    pub bump: u8,                  // 1 byte
+}
+
+/// How a trade fee is divided up.
+pub struct TradeFeeSplit {
+    pub platform: u64,
+    pub creator: u64,
+    pub lp: u64,
+}
+
+impl PlatformConfig {
+    /// Splits `fee` by the configured shares (each rounded down). The
+    /// platform takes the remainder, including the LP share when no provider
+    /// holds shares.
+    pub fn split_trade_fee(&self, fee: u64, has_providers: bool) -> Result<TradeFeeSplit> {
+        let creator = mul_div_floor(fee, self.creator_fee_share_bps, BASIS_POINTS_DIVISOR)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let lp = if has_providers {
+            mul_div_floor(fee, self.lp_fee_share_bps, BASIS_POINTS_DIVISOR)
+                .ok_or(ErrorCode::MathOverflow)?
+        } else {
+            0
+        };
+        let platform = fee
+            .checked_sub(creator)
+            .and_then(|rest| rest.checked_sub(lp))
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(TradeFeeSplit { platform, creator, lp })
+    }
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct TokenConfig {
@@ -741,23 +2087,440 @@ pub struct TokenConfig {
     pub metadata_uri: String,      // 4 + 200 = 204 bytes
     pub total_supply: u64,         // 8 bytes
     pub initial_supply: u64,       // 8 bytes
-    pub sol_reserves: u64,         // 8 bytes
-    pub token_reserves: u64,       // 8 bytes
+    pub curve_type: CurveType,     // 1 byte
+    pub initial_virtual_sol_reserves: u64, // 8 bytes
+    pub initial_virtual_token_reserves: u64, // 8 bytes
+    pub virtual_sol_reserves: u64, // 8 bytes
+    pub real_sol_reserves: u64,    // 8 bytes
+    pub virtual_token_reserves: u64, // 8 bytes
+    pub real_token_reserves: u64,  // 8 bytes
     pub tokens_sold: u64,          // 8 bytes
     pub total_volume: u64,         // 8 bytes
     pub created_at: i64,           // 8 bytes
+    pub graduation_threshold: u64, // 8 bytes
+    pub graduated: bool,           // 1 byte
+    pub creator_fee_bps: u64,      // 8 bytes
+    pub trading_halted: bool,      // 1 byte
+    pub metadata_locked: bool,     // 1 byte
     pub bump: u8,                  // 1 byte
 }
 
+impl TokenConfig {
+    /// SOL and tokens owed to liquidity providers: their shares of the
+    /// virtual reserves, capped at the real reserves.
+    pub fn lp_reserves(&self, lp_account: &LpAccount) -> Result<(u64, u64)> {
+        let provider_liquidity = lp_account.provider_liquidity();
+        let sol = mul_div_floor(self.virtual_sol_reserves, provider_liquidity, lp_account.liquidity)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let tokens = mul_div_floor(self.virtual_token_reserves, provider_liquidity, lp_account.liquidity)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok((sol.min(self.real_sol_reserves), tokens.min(self.real_token_reserves)))
+    }
+
+    /// Real SOL reserves paid in by curve trades, i.e. excluding what
+    /// liquidity providers are owed.
+    pub fn curve_sol_reserves(&self, lp_account: &LpAccount) -> Result<u64> {
+        let (lp_sol, _) = self.lp_reserves(lp_account)?;
+        Ok(self.real_sol_reserves - lp_sol)
+    }
+
+    /// Curve trading stops once the curve's own SOL reaches the graduation
+    /// threshold, even before `graduate` has been called. LP deposits don't
+    /// count towards it.
+    pub fn is_curve_complete(&self, lp_account: &LpAccount) -> Result<bool> {
+        Ok(self.graduated || self.curve_sol_reserves(lp_account)? >= self.graduation_threshold)
+    }
+
+    pub fn curve(&self) -> Curve {
+        Curve {
+            curve_type: self.curve_type.into(),
+            initial_virtual_sol_reserves: self.initial_virtual_sol_reserves,
+            initial_virtual_token_reserves: self.initial_virtual_token_reserves,
+            virtual_sol_reserves: self.virtual_sol_reserves,
+            virtual_token_reserves: self.virtual_token_reserves,
+            tokens_sold: self.tokens_sold,
+        }
+    }
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct LpAccount {
     pub mint: Pubkey,              // 32 bytes
     pub liquidity: u64,            // 8 bytes
+    pub locked_liquidity: u64,     // 8 bytes
+    pub fee_per_share: u128,       // 16 bytes
     pub timestamp: i64,            // 8 bytes
     pub bump: u8,                  // 1 byte
 }
 
+impl LpAccount {
+    /// Shares held by providers, i.e. everything but the locked initial liquidity.
+    pub fn provider_liquidity(&self) -> u64 {
+        self.liquidity.saturating_sub(self.locked_liquidity)
+    }
+
+    /// Spreads `fee` lamports (already held by this account) over the
+    /// provider shares.
+    pub fn accrue_fees(&mut self, fee: u64) -> Result<()> {
+        if fee == 0 {
+            return Ok(());
+        }
+        let increase = (fee as u128 * FEE_PER_SHARE_SCALE)
+            .checked_div(self.provider_liquidity() as u128)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.fee_per_share = self.fee_per_share
+            .checked_add(increase)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+}
+
+/// LP shares held by one provider. Shares in `LpAccount::liquidity` not held
+/// by any position are the curve's initial liquidity and stay locked.
+#[account]
+#[derive(InitSpace)]
+pub struct LpPosition {
+    pub mint: Pubkey,              // 32 bytes
+    pub provider: Pubkey,          // 32 bytes
+    pub shares: u64,               // 8 bytes
+    pub fee_debt: u128,            // 16 bytes
+    pub unclaimed_fees: u64,       // 8 bytes
+    pub bump: u8,                  // 1 byte
+}
+
+impl LpPosition {
+    fn accrued_fees(&self, fee_per_share: u128) -> Result<u128> {
+        Ok((self.shares as u128)
+            .checked_mul(fee_per_share)
+            .ok_or(ErrorCode::MathOverflow)?
+            / FEE_PER_SHARE_SCALE)
+    }
+
+    /// Moves fees accrued since the last settlement into `unclaimed_fees`.
+    /// Call before `shares` changes.
+    pub fn settle_fees(&mut self, fee_per_share: u128) -> Result<()> {
+        let accrued = self.accrued_fees(fee_per_share)?;
+        let pending = u64::try_from(accrued.saturating_sub(self.fee_debt))
+            .map_err(|_| ErrorCode::MathOverflow)?;
+        self.unclaimed_fees = self.unclaimed_fees
+            .checked_add(pending)
+            .ok_or(ErrorCode::MathOverflow)?;
+        // Never lower the debt: `reset_fee_debt` rounds it up.
+        self.fee_debt = self.fee_debt.max(accrued);
+        Ok(())
+    }
+
+    /// Marks everything accrued so far as settled. Call after `shares` changes.
+    /// Rounds up so the floored `accrued_fees` never pays out the remainder.
+    pub fn reset_fee_debt(&mut self, fee_per_share: u128) -> Result<()> {
+        self.fee_debt = (self.shares as u128)
+            .checked_mul(fee_per_share)
+            .ok_or(ErrorCode::MathOverflow)?
+            .div_ceil(FEE_PER_SHARE_SCALE);
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct MigrationVault {
+    pub mint: Pubkey,              // 32 bytes
+    pub sol_amount: u64,           // 8 bytes
+    pub token_amount: u64,         // 8 bytes
+    pub graduated_at: i64,         // 8 bytes
+    pub bump: u8,                  // 1 byte
+}
+
+/// Lamports owed to a token's creator, held on this PDA until claimed.
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorFeeVault {
+    pub mint: Pubkey,              // 32 bytes
+    pub creator: Pubkey,           // 32 bytes
+    pub accrued_fees: u64,         // 8 bytes
+    pub claimed_fees: u64,         // 8 bytes
+    pub bump: u8,                  // 1 byte
+}
+
+impl CreatorFeeVault {
+    /// Records `fee` lamports already moved into the vault.
+    pub fn accrue(&mut self, fee: u64) -> Result<()> {
+        self.accrued_fees = self.accrued_fees
+            .checked_add(fee)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct VestingSchedule {
+    pub mint: Pubkey,                 // 32 bytes
+    pub beneficiary: Pubkey,          // 32 bytes
+    pub start_time: i64,              // 8 bytes
+    pub cliff_seconds: i64,           // 8 bytes
+    pub duration_seconds: i64,        // 8 bytes
+    pub claim_interval_seconds: i64,  // 8 bytes
+    pub total_amount: u64,            // 8 bytes
+    pub claimed_amount: u64,          // 8 bytes
+    pub last_claim_at: i64,           // 8 bytes
+    pub bump: u8,                     // 1 byte
+}
+
+impl VestingSchedule {
+    /// Tokens vested at `now`: nothing before the cliff, then linear in the
+    /// time since `start_time` until `duration_seconds` has passed.
+    pub fn vested_amount(&self, now: i64) -> Result<u64> {
+        let elapsed = now.saturating_sub(self.start_time);
+        if elapsed < self.cliff_seconds {
+            return Ok(0);
+        }
+        if elapsed >= self.duration_seconds {
+            return Ok(self.total_amount);
+        }
+        mul_div_floor(self.total_amount, elapsed as u64, self.duration_seconds as u64)
+            .ok_or(error!(ErrorCode::MathOverflow))
+    }
+}
+
+// ============================================================================
+// EVENTS
+// ============================================================================
+
+#[event]
+pub struct PlatformConfigUpdated {
+    pub admin: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub trade_fee_bps: u64,
+    pub lp_fee_share_bps: u64,
+    pub creator_fee_share_bps: u64,
+    pub max_creator_fee_bps: u64,
+    pub creation_fee: u64,
+    pub graduation_threshold: u64,
+    pub trading_paused: bool,
+    pub creation_paused: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminProposed {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminTransferred {
+    pub previous_admin: Pubkey,
+    pub new_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TradingHaltChanged {
+    pub mint: Pubkey,
+    pub halted: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokenCreated {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub metadata_uri: String,
+    pub total_supply: u64,
+    pub curve_type: CurveType,
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub real_token_reserves: u64,
+    pub creator_allocation: u64,
+    pub locked_allocation: u64,
+    pub creator_fee_bps: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokenMetadataUpdated {
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub metadata_uri: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokenMetadataLocked {
+    pub mint: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokenBought {
+    pub mint: Pubkey,
+    pub trader: Pubkey,
+    /// SOL paid by the trader, including the fee
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    /// Whole trade fee; the platform receives `fee - creator_fee - lp_fee`
+    pub fee: u64,
+    pub creator_fee: u64,
+    pub lp_fee: u64,
+    /// Creator royalty, charged on top of `fee`
+    pub creator_royalty: u64,
+    pub virtual_sol_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub real_token_reserves: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokenSold {
+    pub mint: Pubkey,
+    pub trader: Pubkey,
+    /// SOL received by the trader, after the fee
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    /// Whole trade fee; the platform receives `fee - creator_fee - lp_fee`
+    pub fee: u64,
+    pub creator_fee: u64,
+    pub lp_fee: u64,
+    /// Creator royalty, charged on top of `fee`
+    pub creator_royalty: u64,
+    pub virtual_sol_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub real_token_reserves: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LiquidityAdded {
+    pub mint: Pubkey,
+    pub provider: Pubkey,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub shares: u64,
+    pub position_shares: u64,
+    pub liquidity: u64,
+    pub virtual_sol_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub real_token_reserves: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LiquidityRemoved {
+    pub mint: Pubkey,
+    pub provider: Pubkey,
+    pub lp_share: u64,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub position_shares: u64,
+    pub liquidity: u64,
+    pub virtual_sol_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub real_token_reserves: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokenGraduated {
+    pub mint: Pubkey,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LpFeesClaimed {
+    pub mint: Pubkey,
+    pub provider: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CreatorFeesClaimed {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub claimed_fees: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VestedTokensClaimed {
+    pub mint: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub claimed_amount: u64,
+    pub total_amount: u64,
+    pub timestamp: i64,
+}
+
+// ============================================================================
+// INSTRUCTION PARAMS
+// ============================================================================
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct UpdatePlatformConfigParams {
+    pub trade_fee_bps: Option<u64>,
+    pub lp_fee_share_bps: Option<u64>,
+    pub creator_fee_share_bps: Option<u64>,
+    pub max_creator_fee_bps: Option<u64>,
+    pub creation_fee: Option<u64>,
+    pub fee_recipient: Option<Pubkey>,
+    pub graduation_threshold: Option<u64>,
+    pub trading_paused: Option<bool>,
+    pub creation_paused: Option<bool>,
+}
+
+/// Curve a token is created with. The virtual reserves price the curve but
+/// are never funded: the starting price is
+/// `virtual_sol_reserves / virtual_token_reserves` for every curve type.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CurveParams {
+    pub curve_type: CurveType,
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+}
+
+impl CurveParams {
+    /// Checks the curve can price trades and still holds tokens when its real
+    /// SOL reserves reach `graduation_threshold`.
+    pub fn validate(&self, real_token_reserves: u64, graduation_threshold: u64) -> Result<()> {
+        require!(self.virtual_sol_reserves > 0, ErrorCode::InvalidCurveParams);
+        require!(self.virtual_token_reserves > 0, ErrorCode::InvalidCurveParams);
+        require!(
+            self.virtual_token_reserves >= real_token_reserves,
+            ErrorCode::InvalidCurveParams
+        );
+
+        let curve = Curve {
+            curve_type: self.curve_type.into(),
+            initial_virtual_sol_reserves: self.virtual_sol_reserves,
+            initial_virtual_token_reserves: self.virtual_token_reserves,
+            virtual_sol_reserves: self.virtual_sol_reserves,
+            virtual_token_reserves: self.virtual_token_reserves,
+            tokens_sold: 0,
+        };
+        let tokens_sold_at_graduation = curve
+            .quote_buy(graduation_threshold)
+            .map_err(|_| ErrorCode::InvalidCurveParams)?;
+        require!(
+            tokens_sold_at_graduation < real_token_reserves,
+            ErrorCode::InvalidCurveParams
+        );
+        Ok(())
+    }
+}
+
 // ============================================================================
 // ERROR CODES
 // ============================================================================
@@ -766,6 +2529,8 @@ pub struct LpAccount {
 pub enum ErrorCode {
     #[msg("Invalid amount")]
     InvalidAmount,
+    /// No longer returned: replaced by `InvalidName`, `InvalidSymbol` and
+    /// `InvalidUri`, kept so the codes after it don't shift.
     #[msg("Invalid input")]
     InvalidInput,
     #[msg("Math overflow")]
@@ -776,4 +2541,40 @@ pub enum ErrorCode {
     SlippageExceeded,
     #[msg("Invalid price ratio")]
     InvalidPriceRatio,
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Invalid fee configuration")]
+    InvalidFeeConfig,
+    #[msg("Trading is paused")]
+    TradingPaused,
+    #[msg("Token creation is paused")]
+    CreationPaused,
+    #[msg("Fee recipient does not match platform config")]
+    InvalidFeeRecipient,
+    #[msg("Bonding curve is complete")]
+    CurveComplete,
+    #[msg("Graduation threshold not reached")]
+    GraduationThresholdNotReached,
+    #[msg("Curve lamports do not cover real reserves plus rent")]
+    ReserveInvariantViolated,
+    #[msg("Invalid bonding curve parameters")]
+    InvalidCurveParams,
+    #[msg("Transaction deadline has passed")]
+    DeadlineExceeded,
+    #[msg("No vested tokens to claim")]
+    NothingToClaim,
+    #[msg("Claim interval has not elapsed since the last claim")]
+    ClaimIntervalNotElapsed,
+    #[msg("Token metadata is locked")]
+    MetadataLocked,
+    #[msg("Name must be 1-32 bytes, without surrounding whitespace or control characters")]
+    InvalidName,
+    #[msg("Symbol must be 1-10 ASCII letters or digits")]
+    InvalidSymbol,
+    #[msg("URI must use ipfs://, ar:// or https:// and contain no whitespace")]
+    InvalidUri,
+    #[msg("Liquidity is only supported on constant product curves")]
+    LiquidityUnsupported,
+    #[msg("Creator allocation is locked until the token graduates")]
+    AllocationLocked,
 }
@@ -0,0 +1,80 @@
+//! Trade pricing for the trade instructions.
+//!
+//! Thin wrappers over the `audio_token_quote` crate, which the Rust client
+//! and the web app also use, so a quote built from freshly fetched
+//! `PlatformConfig` and `TokenConfig` accounts is what the instruction will
+//! settle. Pause, deadline and slippage checks are left to the instruction.
+
+use anchor_lang::prelude::*;
+use audio_token_quote::{Pool, QuoteError};
+
+use crate::{ErrorCode, PlatformConfig, TokenConfig};
+
+pub use audio_token_quote::{Side, TradeQuote};
+
+impl From<QuoteError> for ErrorCode {
+    fn from(error: QuoteError) -> Self {
+        match error {
+            QuoteError::InvalidAmount => ErrorCode::InvalidAmount,
+            QuoteError::MathOverflow => ErrorCode::MathOverflow,
+            QuoteError::InsufficientLiquidity => ErrorCode::InsufficientLiquidity,
+        }
+    }
+}
+
+/// The token's curve, reserves and fees as the quote crate sees them.
+pub fn pool(platform_config: &PlatformConfig, token_config: &TokenConfig) -> Pool {
+    Pool {
+        curve: token_config.curve(),
+        real_sol_reserves: token_config.real_sol_reserves,
+        real_token_reserves: token_config.real_token_reserves,
+        trade_fee_bps: platform_config.trade_fee_bps,
+        creator_fee_bps: token_config.creator_fee_bps,
+    }
+}
+
+/// `buy_tokens`: spend `sol_amount` lamports, fees included.
+pub fn buy(
+    platform_config: &PlatformConfig,
+    token_config: &TokenConfig,
+    sol_amount: u64,
+) -> Result<TradeQuote> {
+    let pool = pool(platform_config, token_config);
+    Ok(audio_token_quote::get_buy_quote(&pool, sol_amount).map_err(ErrorCode::from)?)
+}
+
+/// `buy_exact_tokens_out`: receive exactly `token_amount` tokens.
+pub fn buy_exact_tokens_out(
+    platform_config: &PlatformConfig,
+    token_config: &TokenConfig,
+    token_amount: u64,
+) -> Result<TradeQuote> {
+    let pool = pool(platform_config, token_config);
+    Ok(
+        audio_token_quote::get_buy_exact_tokens_quote(&pool, token_amount)
+            .map_err(ErrorCode::from)?,
+    )
+}
+
+/// `sell_tokens`: sell exactly `token_amount` tokens.
+pub fn sell(
+    platform_config: &PlatformConfig,
+    token_config: &TokenConfig,
+    token_amount: u64,
+) -> Result<TradeQuote> {
+    let pool = pool(platform_config, token_config);
+    Ok(audio_token_quote::get_sell_quote(&pool, token_amount).map_err(ErrorCode::from)?)
+}
+
+/// `sell_for_exact_sol`: receive exactly `sol_amount` lamports after fees.
+pub fn sell_for_exact_sol(
+    platform_config: &PlatformConfig,
+    token_config: &TokenConfig,
+    sol_amount: u64,
+) -> Result<TradeQuote> {
+    let pool = pool(platform_config, token_config);
+    Ok(
+        audio_token_quote::get_sell_for_exact_sol_quote(&pool, sol_amount)
+            .map_err(ErrorCode::from)?,
+    )
+}
@@ -0,0 +1,103 @@
+//! Content checks for the token name, symbol and metadata URI.
+//!
+//! Lengths are in bytes, matching the space reserved in `TokenConfig`.
+
+use anchor_lang::prelude::*;
+
+use crate::{ErrorCode, MAX_METADATA_URI_LEN, MAX_NAME_LEN, MAX_SYMBOL_LEN};
+
+/// URI schemes accepted for token metadata.
+pub const ALLOWED_URI_SCHEMES: [&str; 3] = ["ipfs://", "ar://", "https://"];
+
+/// Non-empty, no leading or trailing whitespace and no control characters.
+pub fn validate_name(name: &str) -> Result<()> {
+    require!(
+        !name.is_empty()
+            && name.len() <= MAX_NAME_LEN
+            && name.trim() == name
+            && !name.chars().any(char::is_control),
+        ErrorCode::InvalidName
+    );
+    Ok(())
+}
+
+/// Non-empty and ASCII letters and digits only.
+pub fn validate_symbol(symbol: &str) -> Result<()> {
+    require!(
+        !symbol.is_empty()
+            && symbol.len() <= MAX_SYMBOL_LEN
+            && symbol.bytes().all(|byte| byte.is_ascii_alphanumeric()),
+        ErrorCode::InvalidSymbol
+    );
+    Ok(())
+}
+
+/// An allowed scheme followed by a non-empty path, with no whitespace or
+/// control characters.
+pub fn validate_uri(uri: &str) -> Result<()> {
+    let path = ALLOWED_URI_SCHEMES
+        .iter()
+        .find_map(|scheme| uri.strip_prefix(scheme));
+    require!(
+        uri.len() <= MAX_METADATA_URI_LEN
+            && path.is_some_and(|path| !path.is_empty())
+            && !uri.chars().any(|c| c.is_whitespace() || c.is_control()),
+        ErrorCode::InvalidUri
+    );
+    Ok(())
+}
+
+/// Runs all three checks, as done on creation and on metadata updates.
+pub fn validate_metadata(name: &str, symbol: &str, uri: &str) -> Result<()> {
+    validate_name(name)?;
+    validate_symbol(symbol)?;
+    validate_uri(uri)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_must_be_trimmed_printable_and_non_empty() {
+        assert!(validate_name("Noiz Sound").is_ok());
+        assert!(validate_name("Sön 🎵").is_ok());
+        assert!(validate_name(&"n".repeat(MAX_NAME_LEN)).is_ok());
+        for name in ["", "   ", " Noiz", "Noiz ", "No\niz", "No\u{7}iz"] {
+            assert!(validate_name(name).is_err(), "{name:?}");
+        }
+        assert!(validate_name(&"n".repeat(MAX_NAME_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn symbols_are_ascii_alphanumeric() {
+        assert!(validate_symbol("NOIZ").is_ok());
+        assert!(validate_symbol("beat808").is_ok());
+        for symbol in ["", " ", "NO IZ", "NOIZ!", "$NOIZ", "NÖIZ", "NOIZNOIZNOIZ"] {
+            assert!(validate_symbol(symbol).is_err(), "{symbol:?}");
+        }
+    }
+
+    #[test]
+    fn uris_need_an_allowed_scheme() {
+        for uri in [
+            "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+            "ar://Y0wJvUkHFhcJZAduC8wfaiaDMHkrCoqHMSkenHD75VU",
+            "https://example.com/metadata.json",
+        ] {
+            assert!(validate_uri(uri).is_ok(), "{uri:?}");
+        }
+        for uri in [
+            "",
+            "ipfs://",
+            "http://example.com/metadata.json",
+            "javascript:alert(1)",
+            "ipfs://bafy beig",
+            "https://example.com/\n",
+        ] {
+            assert!(validate_uri(uri).is_err(), "{uri:?}");
+        }
+        let long = format!("https://{}", "a".repeat(MAX_METADATA_URI_LEN));
+        assert!(validate_uri(&long).is_err());
+    }
+}
@@ -0,0 +1,180 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use audio_token_platform::{ErrorCode, UpdatePlatformConfigParams};
+use common::*;
+
+fn pause_ix(admin: &Pubkey) -> anchor_lang::solana_program::instruction::Instruction {
+    update_platform_config_ix(
+        admin,
+        UpdatePlatformConfigParams {
+            trading_paused: Some(true),
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+fn admin_transfer_takes_effect_on_accept() {
+    let mut fixture = Fixture::new();
+    let admin = fixture.admin;
+    let new_admin = fixture.funded_wallet(LAMPORTS_PER_SOL);
+
+    fixture
+        .svm
+        .send(&[propose_admin_ix(&admin, &new_admin)], &[&admin])
+        .unwrap();
+    let platform_config = fixture.platform_config();
+    assert_eq!(platform_config.admin, admin);
+    assert_eq!(platform_config.pending_admin, Some(new_admin));
+
+    fixture
+        .svm
+        .send(&[accept_admin_ix(&new_admin)], &[&new_admin])
+        .unwrap();
+    let platform_config = fixture.platform_config();
+    assert_eq!(platform_config.admin, new_admin);
+    assert_eq!(platform_config.pending_admin, None);
+
+    assert_error(
+        fixture.svm.send(&[pause_ix(&admin)], &[&admin]),
+        ErrorCode::Unauthorized,
+    );
+    fixture
+        .svm
+        .send(&[pause_ix(&new_admin)], &[&new_admin])
+        .unwrap();
+}
+
+#[test]
+fn only_the_admin_can_propose() {
+    let mut fixture = Fixture::new();
+    let attacker = fixture.funded_wallet(LAMPORTS_PER_SOL);
+
+    let result = fixture
+        .svm
+        .send(&[propose_admin_ix(&attacker, &attacker)], &[&attacker]);
+
+    assert_error(result, ErrorCode::Unauthorized);
+    assert_eq!(fixture.platform_config().pending_admin, None);
+}
+
+#[test]
+fn only_the_pending_admin_can_accept() {
+    let mut fixture = Fixture::new();
+    let admin = fixture.admin;
+    let new_admin = fixture.funded_wallet(LAMPORTS_PER_SOL);
+    let attacker = fixture.funded_wallet(LAMPORTS_PER_SOL);
+    assert_error(
+        fixture
+            .svm
+            .send(&[accept_admin_ix(&attacker)], &[&attacker]),
+        ErrorCode::Unauthorized,
+    );
+
+    fixture
+        .svm
+        .send(&[propose_admin_ix(&admin, &new_admin)], &[&admin])
+        .unwrap();
+    assert_error(
+        fixture
+            .svm
+            .send(&[accept_admin_ix(&attacker)], &[&attacker]),
+        ErrorCode::Unauthorized,
+    );
+
+    // A new proposal replaces the pending one
+    fixture
+        .svm
+        .send(&[propose_admin_ix(&admin, &attacker)], &[&admin])
+        .unwrap();
+    assert_error(
+        fixture
+            .svm
+            .send(&[accept_admin_ix(&new_admin)], &[&new_admin]),
+        ErrorCode::Unauthorized,
+    );
+}
+
+#[test]
+fn unfunded_pda_can_act_as_admin() {
+    let mut fixture = Fixture::new();
+    let admin = fixture.admin;
+    // Stands in for a multisig PDA: it holds no lamports and only ever signs
+    let (multisig, _) = Pubkey::find_program_address(&[b"multisig"], &Pubkey::new_unique());
+
+    fixture
+        .svm
+        .send(&[propose_admin_ix(&admin, &multisig)], &[&admin])
+        .unwrap();
+    fixture
+        .svm
+        .send(&[accept_admin_ix(&multisig)], &[&multisig])
+        .unwrap();
+
+    let (_, mint) = fixture.create_token();
+    fixture
+        .svm
+        .send(&[pause_ix(&multisig)], &[&multisig])
+        .unwrap();
+    fixture
+        .svm
+        .send(
+            &[set_trading_halted_ix(&multisig, &mint, true)],
+            &[&multisig],
+        )
+        .unwrap();
+    assert_eq!(fixture.svm.lamports(&multisig), 0);
+    assert!(fixture.platform_config().trading_paused);
+}
+
+#[test]
+fn unfunded_pda_can_initialize_the_platform() {
+    let mut svm = Svm::new();
+    // An upgrade authority held by a multisig: a separate payer funds the config
+    let (multisig, _) = Pubkey::find_program_address(&[b"multisig"], &Pubkey::new_unique());
+    let payer = Pubkey::new_unique();
+    svm.deploy_program(audio_token_platform::ID, multisig);
+    svm.airdrop(&payer, LAMPORTS_PER_SOL);
+
+    let ix = initialize_platform_ix(
+        &payer,
+        &multisig,
+        TRADE_FEE_BPS,
+        CREATION_FEE,
+        &Pubkey::new_unique(),
+        GRADUATION_THRESHOLD,
+    );
+    svm.send(&[ix], &[&payer, &multisig]).unwrap();
+
+    assert_eq!(svm.lamports(&multisig), 0);
+}
+
+#[test]
+fn admin_accounts_are_never_writable() {
+    let admin = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let instructions = [
+        initialize_platform_ix(
+            &Pubkey::new_unique(),
+            &admin,
+            TRADE_FEE_BPS,
+            CREATION_FEE,
+            &Pubkey::new_unique(),
+            GRADUATION_THRESHOLD,
+        ),
+        update_platform_config_ix(&admin, UpdatePlatformConfigParams::default()),
+        propose_admin_ix(&admin, &Pubkey::new_unique()),
+        accept_admin_ix(&admin),
+        set_trading_halted_ix(&admin, &mint, true),
+    ];
+
+    for ix in instructions {
+        let meta = ix
+            .accounts
+            .iter()
+            .find(|meta| meta.pubkey == admin)
+            .unwrap();
+        assert!(meta.is_signer && !meta.is_writable);
+    }
+}
@@ -0,0 +1,650 @@
+//! Shared fixtures and instruction builders for the program tests.
+
+#![allow(dead_code)]
+
+pub mod svm;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{instruction::Instruction, system_program, sysvar};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::{self, get_associated_token_address};
+use anchor_spl::metadata::mpl_token_metadata;
+use anchor_spl::token::spl_token;
+use audio_token_platform::{
+    accounts, instruction, CurveParams, CurveType, ErrorCode, PlatformConfig, TokenConfig,
+    UpdatePlatformConfigParams, CREATOR_FEE_VAULT_SEED, CREATOR_VAULT_SEED, LOCKED_VAULT_SEED,
+    LP_ACCOUNT_SEED, LP_POSITION_SEED, MIGRATION_VAULT_SEED, PLATFORM_CONFIG_SEED,
+    TOKEN_CONFIG_SEED, VESTING_SCHEDULE_SEED,
+};
+
+pub use svm::{metadata_address, program_data_address, Svm, TxError};
+
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+pub const TRADE_FEE_BPS: u64 = 25;
+pub const CREATION_FEE: u64 = 20_000_000;
+pub const GRADUATION_THRESHOLD: u64 = LAMPORTS_PER_SOL / 10;
+pub const TOTAL_SUPPLY: u64 = 1_000_000_000 * 1_000_000_000;
+/// Just above the 10% of `TOTAL_SUPPLY` held by the curve, so the curve still
+/// holds tokens at `GRADUATION_THRESHOLD`.
+pub const CURVE_PARAMS: CurveParams = CurveParams {
+    curve_type: CurveType::ConstantProduct,
+    virtual_sol_reserves: LAMPORTS_PER_SOL / 100,
+    virtual_token_reserves: 105_000_000 * 1_000_000_000,
+};
+
+pub fn platform_config_address() -> Pubkey {
+    Pubkey::find_program_address(&[PLATFORM_CONFIG_SEED], &audio_token_platform::ID).0
+}
+
+pub fn token_config_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[TOKEN_CONFIG_SEED, mint.as_ref()],
+        &audio_token_platform::ID,
+    )
+    .0
+}
+
+pub fn lp_account_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[LP_ACCOUNT_SEED, mint.as_ref()], &audio_token_platform::ID).0
+}
+
+pub fn reserve_address(mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(&token_config_address(mint), mint)
+}
+
+pub fn creator_vault_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[CREATOR_VAULT_SEED, mint.as_ref()],
+        &audio_token_platform::ID,
+    )
+    .0
+}
+
+pub fn locked_vault_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[LOCKED_VAULT_SEED, mint.as_ref()],
+        &audio_token_platform::ID,
+    )
+    .0
+}
+
+pub fn creator_fee_vault_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[CREATOR_FEE_VAULT_SEED, mint.as_ref()],
+        &audio_token_platform::ID,
+    )
+    .0
+}
+
+pub fn lp_position_address(mint: &Pubkey, provider: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[LP_POSITION_SEED, mint.as_ref(), provider.as_ref()],
+        &audio_token_platform::ID,
+    )
+    .0
+}
+
+pub fn vesting_schedule_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[VESTING_SCHEDULE_SEED, mint.as_ref()],
+        &audio_token_platform::ID,
+    )
+    .0
+}
+
+pub fn migration_vault_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[MIGRATION_VAULT_SEED, mint.as_ref()],
+        &audio_token_platform::ID,
+    )
+    .0
+}
+
+pub fn assert_error(result: std::result::Result<(), TxError>, error: ErrorCode) {
+    assert_eq!(
+        result,
+        Err(TxError::Program(ProgramError::Custom(error.into()))),
+        "expected {error:?}"
+    );
+}
+
+// ============================================================================
+// INSTRUCTION BUILDERS
+// ============================================================================
+
+pub fn initialize_platform_ix(
+    payer: &Pubkey,
+    admin: &Pubkey,
+    trade_fee_bps: u64,
+    creation_fee: u64,
+    fee_recipient: &Pubkey,
+    graduation_threshold: u64,
+) -> Instruction {
+    Instruction {
+        program_id: audio_token_platform::ID,
+        accounts: accounts::InitializePlatform {
+            platform_config: platform_config_address(),
+            program: audio_token_platform::ID,
+            program_data: program_data_address(&audio_token_platform::ID),
+            admin: *admin,
+            payer: *payer,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::InitializePlatform {
+            trade_fee_bps,
+            creation_fee,
+            fee_recipient: *fee_recipient,
+            graduation_threshold,
+        }
+        .data(),
+    }
+}
+
+pub fn update_platform_config_ix(
+    admin: &Pubkey,
+    params: UpdatePlatformConfigParams,
+) -> Instruction {
+    Instruction {
+        program_id: audio_token_platform::ID,
+        accounts: accounts::UpdatePlatformConfig {
+            platform_config: platform_config_address(),
+            admin: *admin,
+        }
+        .to_account_metas(None),
+        data: instruction::UpdatePlatformConfig { params }.data(),
+    }
+}
+
+pub fn propose_admin_ix(admin: &Pubkey, new_admin: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: audio_token_platform::ID,
+        accounts: accounts::ProposeAdmin {
+            platform_config: platform_config_address(),
+            admin: *admin,
+        }
+        .to_account_metas(None),
+        data: instruction::ProposeAdmin {
+            new_admin: *new_admin,
+        }
+        .data(),
+    }
+}
+
+pub fn accept_admin_ix(pending_admin: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: audio_token_platform::ID,
+        accounts: accounts::AcceptAdmin {
+            platform_config: platform_config_address(),
+            pending_admin: *pending_admin,
+        }
+        .to_account_metas(None),
+        data: instruction::AcceptAdmin {}.data(),
+    }
+}
+
+pub fn set_trading_halted_ix(admin: &Pubkey, mint: &Pubkey, halted: bool) -> Instruction {
+    Instruction {
+        program_id: audio_token_platform::ID,
+        accounts: accounts::SetTradingHalted {
+            platform_config: platform_config_address(),
+            token_config: token_config_address(mint),
+            mint: *mint,
+            admin: *admin,
+        }
+        .to_account_metas(None),
+        data: instruction::SetTradingHalted { halted }.data(),
+    }
+}
+
+pub fn create_audio_token_ix(
+    creator: &Pubkey,
+    mint: &Pubkey,
+    platform_fee_account: &Pubkey,
+    total_supply: u64,
+    curve_params: CurveParams,
+    creator_fee_bps: u64,
+) -> Instruction {
+    Instruction {
+        program_id: audio_token_platform::ID,
+        accounts: accounts::CreateAudioToken {
+            token_config: token_config_address(mint),
+            lp_account: lp_account_address(mint),
+            mint: *mint,
+            reserve_token_account: reserve_address(mint),
+            creator_vault: creator_vault_address(mint),
+            locked_vault: locked_vault_address(mint),
+            vesting_schedule: vesting_schedule_address(mint),
+            creator_fee_vault: creator_fee_vault_address(mint),
+            metadata_account: metadata_address(mint),
+            creator: *creator,
+            token_metadata_program: mpl_token_metadata::ID,
+            platform_config: platform_config_address(),
+            platform_fee_account: *platform_fee_account,
+            system_program: system_program::ID,
+            token_program: spl_token::ID,
+            associated_token_program: associated_token::ID,
+            rent: sysvar::rent::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::CreateAudioToken {
+            name: "Noiz Sound".to_string(),
+            symbol: "NOIZ".to_string(),
+            metadata_uri: "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"
+                .to_string(),
+            total_supply,
+            curve_params,
+            creator_fee_bps,
+        }
+        .data(),
+    }
+}
+
+pub fn update_token_metadata_ix(
+    creator: &Pubkey,
+    mint: &Pubkey,
+    name: &str,
+    symbol: &str,
+    metadata_uri: &str,
+) -> Instruction {
+    Instruction {
+        program_id: audio_token_platform::ID,
+        accounts: update_token_metadata_accounts(creator, mint),
+        data: instruction::UpdateTokenMetadata {
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            metadata_uri: metadata_uri.to_string(),
+        }
+        .data(),
+    }
+}
+
+pub fn lock_metadata_ix(creator: &Pubkey, mint: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: audio_token_platform::ID,
+        accounts: update_token_metadata_accounts(creator, mint),
+        data: instruction::LockMetadata {}.data(),
+    }
+}
+
+fn update_token_metadata_accounts(creator: &Pubkey, mint: &Pubkey) -> Vec<AccountMeta> {
+    accounts::UpdateTokenMetadata {
+        token_config: token_config_address(mint),
+        mint: *mint,
+        metadata_account: metadata_address(mint),
+        creator: *creator,
+        token_metadata_program: mpl_token_metadata::ID,
+    }
+    .to_account_metas(None)
+}
+
+pub fn buy_tokens_ix(
+    buyer: &Pubkey,
+    mint: &Pubkey,
+    platform_fee_account: &Pubkey,
+    sol_amount: u64,
+    min_tokens_out: u64,
+    deadline: Option<i64>,
+) -> Instruction {
+    Instruction {
+        program_id: audio_token_platform::ID,
+        accounts: accounts::BuyTokens {
+            token_config: token_config_address(mint),
+            lp_account: lp_account_address(mint),
+            mint: *mint,
+            reserve_token_account: reserve_address(mint),
+            buyer_token_account: get_associated_token_address(buyer, mint),
+            buyer: *buyer,
+            platform_config: platform_config_address(),
+            platform_fee_account: *platform_fee_account,
+            creator_fee_vault: creator_fee_vault_address(mint),
+            system_program: system_program::ID,
+            token_program: spl_token::ID,
+            associated_token_program: associated_token::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::BuyTokens {
+            sol_amount,
+            min_tokens_out,
+            deadline,
+        }
+        .data(),
+    }
+}
+
+pub fn sell_tokens_ix(
+    seller: &Pubkey,
+    mint: &Pubkey,
+    platform_fee_account: &Pubkey,
+    token_amount: u64,
+    min_sol_out: u64,
+    deadline: Option<i64>,
+) -> Instruction {
+    Instruction {
+        program_id: audio_token_platform::ID,
+        accounts: accounts::SellTokens {
+            token_config: token_config_address(mint),
+            lp_account: lp_account_address(mint),
+            mint: *mint,
+            reserve_token_account: reserve_address(mint),
+            seller_token_account: get_associated_token_address(seller, mint),
+            seller: *seller,
+            platform_config: platform_config_address(),
+            platform_fee_account: *platform_fee_account,
+            creator_fee_vault: creator_fee_vault_address(mint),
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::SellTokens {
+            token_amount,
+            min_sol_out,
+            deadline,
+        }
+        .data(),
+    }
+}
+
+pub fn buy_exact_tokens_out_ix(
+    buyer: &Pubkey,
+    mint: &Pubkey,
+    platform_fee_account: &Pubkey,
+    token_amount: u64,
+    max_sol_in: u64,
+    deadline: Option<i64>,
+) -> Instruction {
+    Instruction {
+        data: instruction::BuyExactTokensOut {
+            token_amount,
+            max_sol_in,
+            deadline,
+        }
+        .data(),
+        ..buy_tokens_ix(buyer, mint, platform_fee_account, 0, 0, None)
+    }
+}
+
+pub fn sell_for_exact_sol_ix(
+    seller: &Pubkey,
+    mint: &Pubkey,
+    platform_fee_account: &Pubkey,
+    sol_amount: u64,
+    max_tokens_in: u64,
+    deadline: Option<i64>,
+) -> Instruction {
+    Instruction {
+        data: instruction::SellForExactSol {
+            sol_amount,
+            max_tokens_in,
+            deadline,
+        }
+        .data(),
+        ..sell_tokens_ix(seller, mint, platform_fee_account, 0, 0, None)
+    }
+}
+
+pub fn add_liquidity_ix(
+    provider: &Pubkey,
+    mint: &Pubkey,
+    max_sol_in: u64,
+    max_tokens_in: u64,
+    min_shares_out: u64,
+) -> Instruction {
+    Instruction {
+        program_id: audio_token_platform::ID,
+        accounts: accounts::AddLiquidity {
+            token_config: token_config_address(mint),
+            lp_account: lp_account_address(mint),
+            lp_position: lp_position_address(mint, provider),
+            mint: *mint,
+            reserve_token_account: reserve_address(mint),
+            provider_token_account: get_associated_token_address(provider, mint),
+            lp_provider: *provider,
+            platform_config: platform_config_address(),
+            token_program: spl_token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::AddLiquidity {
+            max_sol_in,
+            max_tokens_in,
+            min_shares_out,
+        }
+        .data(),
+    }
+}
+
+pub fn remove_liquidity_ix(
+    provider: &Pubkey,
+    mint: &Pubkey,
+    lp_share: u64,
+    min_sol_out: u64,
+    min_tokens_out: u64,
+) -> Instruction {
+    Instruction {
+        program_id: audio_token_platform::ID,
+        accounts: accounts::RemoveLiquidity {
+            token_config: token_config_address(mint),
+            lp_account: lp_account_address(mint),
+            lp_position: lp_position_address(mint, provider),
+            mint: *mint,
+            reserve_token_account: reserve_address(mint),
+            provider_token_account: get_associated_token_address(provider, mint),
+            lp_provider: *provider,
+            platform_config: platform_config_address(),
+            token_program: spl_token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::RemoveLiquidity {
+            lp_share,
+            min_sol_out,
+            min_tokens_out,
+        }
+        .data(),
+    }
+}
+
+pub fn graduate_ix(payer: &Pubkey, mint: &Pubkey) -> Instruction {
+    let migration_vault = migration_vault_address(mint);
+    Instruction {
+        program_id: audio_token_platform::ID,
+        accounts: accounts::Graduate {
+            token_config: token_config_address(mint),
+            lp_account: lp_account_address(mint),
+            mint: *mint,
+            reserve_token_account: reserve_address(mint),
+            locked_vault: locked_vault_address(mint),
+            migration_vault,
+            vault_token_account: get_associated_token_address(&migration_vault, mint),
+            payer: *payer,
+            system_program: system_program::ID,
+            token_program: spl_token::ID,
+            associated_token_program: associated_token::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::Graduate {}.data(),
+    }
+}
+
+pub fn claim_lp_fees_ix(provider: &Pubkey, mint: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: audio_token_platform::ID,
+        accounts: accounts::ClaimLpFees {
+            lp_account: lp_account_address(mint),
+            lp_position: lp_position_address(mint, provider),
+            mint: *mint,
+            lp_provider: *provider,
+        }
+        .to_account_metas(None),
+        data: instruction::ClaimLpFees {}.data(),
+    }
+}
+
+pub fn claim_creator_fees_ix(creator: &Pubkey, mint: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: audio_token_platform::ID,
+        accounts: accounts::ClaimCreatorFees {
+            creator_fee_vault: creator_fee_vault_address(mint),
+            mint: *mint,
+            creator: *creator,
+        }
+        .to_account_metas(None),
+        data: instruction::ClaimCreatorFees {}.data(),
+    }
+}
+
+pub fn claim_vested_ix(beneficiary: &Pubkey, mint: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: audio_token_platform::ID,
+        accounts: accounts::ClaimVested {
+            vesting_schedule: vesting_schedule_address(mint),
+            token_config: token_config_address(mint),
+            mint: *mint,
+            creator_vault: creator_vault_address(mint),
+            beneficiary_token_account: get_associated_token_address(beneficiary, mint),
+            beneficiary: *beneficiary,
+            system_program: system_program::ID,
+            token_program: spl_token::ID,
+            associated_token_program: associated_token::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::ClaimVested {}.data(),
+    }
+}
+
+// ============================================================================
+// FIXTURE
+// ============================================================================
+
+/// A deployed program with an initialized platform config.
+pub struct Fixture {
+    pub svm: Svm,
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+}
+
+impl Fixture {
+    pub fn new() -> Self {
+        let mut svm = Svm::new();
+        let admin = Pubkey::new_unique();
+        let treasury = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+
+        svm.deploy_program(audio_token_platform::ID, admin);
+        svm.airdrop(&admin, 10 * LAMPORTS_PER_SOL);
+        svm.airdrop(&treasury, LAMPORTS_PER_SOL);
+        svm.airdrop(&payer, LAMPORTS_PER_SOL);
+        svm.send(
+            &[initialize_platform_ix(
+                &payer,
+                &admin,
+                TRADE_FEE_BPS,
+                CREATION_FEE,
+                &treasury,
+                GRADUATION_THRESHOLD,
+            )],
+            &[&payer, &admin],
+        )
+        .expect("initialize_platform failed");
+
+        Self {
+            svm,
+            admin,
+            treasury,
+        }
+    }
+
+    pub fn funded_wallet(&mut self, lamports: u64) -> Pubkey {
+        let wallet = Pubkey::new_unique();
+        self.svm.airdrop(&wallet, lamports);
+        wallet
+    }
+
+    pub fn update_platform_config(&mut self, params: UpdatePlatformConfigParams) {
+        let admin = self.admin;
+        self.svm
+            .send(&[update_platform_config_ix(&admin, params)], &[&admin])
+            .expect("update_platform_config failed");
+    }
+
+    pub fn platform_config(&self) -> PlatformConfig {
+        self.svm.anchor_account(&platform_config_address())
+    }
+
+    pub fn token_config(&self, mint: &Pubkey) -> TokenConfig {
+        self.svm.anchor_account(&token_config_address(mint))
+    }
+
+    /// Creates a token from a fresh creator wallet and returns `(creator, mint)`.
+    pub fn create_token(&mut self) -> (Pubkey, Pubkey) {
+        self.create_token_with(CURVE_PARAMS)
+    }
+
+    pub fn create_token_with(&mut self, curve_params: CurveParams) -> (Pubkey, Pubkey) {
+        self.create_token_from(curve_params, 0)
+    }
+
+    /// Creates a token that charges a `creator_fee_bps` royalty on trades.
+    pub fn create_token_with_royalty(&mut self, creator_fee_bps: u64) -> (Pubkey, Pubkey) {
+        self.create_token_from(CURVE_PARAMS, creator_fee_bps)
+    }
+
+    /// Creates a token on `curve_params` that charges a `creator_fee_bps`
+    /// royalty on trades.
+    pub fn create_token_from(
+        &mut self,
+        curve_params: CurveParams,
+        creator_fee_bps: u64,
+    ) -> (Pubkey, Pubkey) {
+        let creator = self.funded_wallet(10 * LAMPORTS_PER_SOL);
+        let mint = Pubkey::new_unique();
+        self.svm
+            .send(
+                &[create_audio_token_ix(
+                    &creator,
+                    &mint,
+                    &self.treasury,
+                    TOTAL_SUPPLY,
+                    curve_params,
+                    creator_fee_bps,
+                )],
+                &[&creator, &mint],
+            )
+            .expect("create_audio_token failed");
+        (creator, mint)
+    }
+
+    pub fn buy(
+        &mut self,
+        buyer: &Pubkey,
+        mint: &Pubkey,
+        sol_amount: u64,
+    ) -> std::result::Result<(), TxError> {
+        let ix = buy_tokens_ix(buyer, mint, &self.treasury, sol_amount, 0, None);
+        self.svm.send(&[ix], &[buyer])
+    }
+
+    pub fn sell(
+        &mut self,
+        seller: &Pubkey,
+        mint: &Pubkey,
+        token_amount: u64,
+    ) -> std::result::Result<(), TxError> {
+        let ix = sell_tokens_ix(seller, mint, &self.treasury, token_amount, 0, None);
+        self.svm.send(&[ix], &[seller])
+    }
+
+    /// Buys past the graduation threshold from a fresh wallet, then
+    /// graduates the token.
+    pub fn graduate(&mut self, mint: &Pubkey) {
+        let trader = self.funded_wallet(10 * LAMPORTS_PER_SOL);
+        // Only the post-fee amount counts towards real reserves
+        self.buy(&trader, mint, GRADUATION_THRESHOLD * 101 / 100)
+            .expect("graduating buy failed");
+        let payer = self.funded_wallet(LAMPORTS_PER_SOL);
+        self.svm
+            .send(&[graduate_ix(&payer, mint)], &[&payer])
+            .expect("graduate failed");
+    }
+}
@@ -0,0 +1,726 @@
+//! Minimal in-process SVM used by the program tests.
+//!
+//! Instructions are serialized into the same input buffer layout the BPF
+//! loader uses and handed to the program natively. CPIs are routed through
+//! `program_stubs` to a small System program, the real SPL Token and
+//! Associated Token Account processors, and a fake Metaplex program that
+//! stores the `DataV2` it receives.
+//!
+//! This stands in for LiteSVM / `solana-program-test` until they can be
+//! built here: it neither runs the SBF binary nor meters compute units (see
+//! "Known Gaps" in the README).
+
+#![allow(dead_code)]
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::mem::size_of;
+use std::sync::Once;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    entrypoint::{deserialize, ProgramResult, MAX_PERMITTED_DATA_INCREASE, NON_DUP_MARKER},
+    instruction::Instruction,
+    program_pack::Pack,
+    program_stubs::{self, SyscallStubs},
+    system_instruction::SystemInstruction,
+    system_program, sysvar,
+};
+use anchor_lang::Discriminator;
+use anchor_spl::associated_token::spl_associated_token_account;
+use anchor_spl::metadata::mpl_token_metadata::{
+    self,
+    instructions::{
+        CreateMetadataAccountV3InstructionArgs, UpdateMetadataAccountV2InstructionArgs,
+    },
+    types::DataV2,
+};
+use anchor_spl::token::spl_token;
+
+pub const DEFAULT_UNIX_TIMESTAMP: i64 = 1_700_000_000;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AccountState {
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub owner: Pubkey,
+    pub executable: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum TxError {
+    MissingSignature(Pubkey),
+    Program(ProgramError),
+    ReadonlyModified(Pubkey),
+    InsufficientFundsForRent(Pubkey),
+    UnbalancedInstruction,
+}
+
+/// Metadata account layout written by the fake Metaplex program.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct FakeMetadata {
+    pub update_authority: Pubkey,
+    pub mint: Pubkey,
+    pub data: DataV2,
+    pub is_mutable: bool,
+}
+
+pub struct Svm {
+    accounts: HashMap<Pubkey, AccountState>,
+    clock: Clock,
+    logs: Vec<String>,
+    log_data: Vec<Vec<u8>>,
+}
+
+#[derive(Default)]
+struct InvokeContext {
+    clock: Clock,
+    program_stack: Vec<Pubkey>,
+    return_data: Option<(Pubkey, Vec<u8>)>,
+    logs: Vec<String>,
+    log_data: Vec<Vec<u8>>,
+}
+
+thread_local! {
+    static CONTEXT: RefCell<InvokeContext> = RefCell::new(InvokeContext::default());
+}
+
+static INSTALL_STUBS: Once = Once::new();
+
+impl Default for Svm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Svm {
+    pub fn new() -> Self {
+        INSTALL_STUBS.call_once(|| {
+            program_stubs::set_syscall_stubs(Box::new(Stubs));
+        });
+
+        let mut svm = Self {
+            accounts: HashMap::new(),
+            clock: Clock {
+                unix_timestamp: DEFAULT_UNIX_TIMESTAMP,
+                ..Clock::default()
+            },
+            logs: Vec::new(),
+            log_data: Vec::new(),
+        };
+
+        for program_id in [
+            system_program::ID,
+            spl_token::ID,
+            spl_associated_token_account::ID,
+            mpl_token_metadata::ID,
+        ] {
+            svm.set_account(
+                program_id,
+                AccountState {
+                    lamports: 1,
+                    data: Vec::new(),
+                    owner: bpf_loader_upgradeable::ID,
+                    executable: true,
+                },
+            );
+        }
+
+        let rent_data = bincode::serialize(&Rent::default()).unwrap();
+        svm.set_account(
+            sysvar::rent::ID,
+            AccountState {
+                lamports: Rent::default().minimum_balance(rent_data.len()),
+                data: rent_data,
+                owner: sysvar::ID,
+                executable: false,
+            },
+        );
+
+        svm
+    }
+
+    /// Deploys `program_id` as an upgradeable program owned by `upgrade_authority`.
+    pub fn deploy_program(&mut self, program_id: Pubkey, upgrade_authority: Pubkey) {
+        let rent = Rent::default();
+        let programdata_address = program_data_address(&program_id);
+
+        let program_data = bincode::serialize(&UpgradeableLoaderState::Program {
+            programdata_address,
+        })
+        .unwrap();
+        self.set_account(
+            program_id,
+            AccountState {
+                lamports: rent.minimum_balance(program_data.len()),
+                data: program_data,
+                owner: bpf_loader_upgradeable::ID,
+                executable: true,
+            },
+        );
+
+        let programdata_data = bincode::serialize(&UpgradeableLoaderState::ProgramData {
+            slot: 0,
+            upgrade_authority_address: Some(upgrade_authority),
+        })
+        .unwrap();
+        self.set_account(
+            programdata_address,
+            AccountState {
+                lamports: rent.minimum_balance(programdata_data.len()),
+                data: programdata_data,
+                owner: bpf_loader_upgradeable::ID,
+                executable: false,
+            },
+        );
+    }
+
+    pub fn set_account(&mut self, key: Pubkey, account: AccountState) {
+        self.accounts.insert(key, account);
+    }
+
+    pub fn account(&self, key: &Pubkey) -> Option<&AccountState> {
+        self.accounts.get(key)
+    }
+
+    pub fn airdrop(&mut self, key: &Pubkey, lamports: u64) {
+        let account = self.accounts.entry(*key).or_default();
+        account.lamports += lamports;
+    }
+
+    pub fn lamports(&self, key: &Pubkey) -> u64 {
+        self.accounts.get(key).map_or(0, |account| account.lamports)
+    }
+
+    pub fn token_balance(&self, key: &Pubkey) -> u64 {
+        let account = self.accounts.get(key).expect("token account not found");
+        spl_token::state::Account::unpack(&account.data)
+            .expect("not a token account")
+            .amount
+    }
+
+    pub fn mint(&self, key: &Pubkey) -> spl_token::state::Mint {
+        let account = self.accounts.get(key).expect("mint not found");
+        spl_token::state::Mint::unpack(&account.data).expect("not a mint")
+    }
+
+    pub fn anchor_account<T: AccountDeserialize>(&self, key: &Pubkey) -> T {
+        let account = self.accounts.get(key).expect("account not found");
+        T::try_deserialize(&mut account.data.as_slice()).expect("failed to deserialize account")
+    }
+
+    pub fn metadata(&self, mint: &Pubkey) -> FakeMetadata {
+        let account = self
+            .accounts
+            .get(&metadata_address(mint))
+            .expect("metadata not found");
+        FakeMetadata::deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    pub fn clock(&self) -> &Clock {
+        &self.clock
+    }
+
+    pub fn set_unix_timestamp(&mut self, unix_timestamp: i64) {
+        self.clock.unix_timestamp = unix_timestamp;
+    }
+
+    pub fn advance_time(&mut self, seconds: i64) {
+        self.clock.unix_timestamp += seconds;
+        self.clock.slot += 1;
+    }
+
+    /// Logs emitted by the most recent transaction.
+    pub fn logs(&self) -> &[String] {
+        &self.logs
+    }
+
+    /// Anchor events of type `T` emitted by the most recent transaction.
+    pub fn events<T: AnchorDeserialize + Discriminator>(&self) -> Vec<T> {
+        self.log_data
+            .iter()
+            .filter(|data| data.starts_with(&T::DISCRIMINATOR))
+            .map(|data| T::deserialize(&mut &data[8..]).expect("failed to deserialize event"))
+            .collect()
+    }
+
+    /// Executes `instructions` atomically: either every instruction succeeds
+    /// and all account changes are committed, or nothing changes.
+    pub fn send(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Pubkey],
+    ) -> std::result::Result<(), TxError> {
+        let mut working = self.accounts.clone();
+        self.logs.clear();
+        self.log_data.clear();
+
+        let mut result = Ok(());
+        for instruction in instructions {
+            result = self.process_instruction(&mut working, instruction, signers);
+            if result.is_err() {
+                break;
+            }
+        }
+
+        if result.is_ok() {
+            self.accounts = working;
+        }
+        result
+    }
+
+    fn process_instruction(
+        &mut self,
+        accounts: &mut HashMap<Pubkey, AccountState>,
+        instruction: &Instruction,
+        signers: &[&Pubkey],
+    ) -> std::result::Result<(), TxError> {
+        for meta in &instruction.accounts {
+            if meta.is_signer && !signers.contains(&&meta.pubkey) {
+                return Err(TxError::MissingSignature(meta.pubkey));
+            }
+        }
+
+        let mut unique_keys: Vec<Pubkey> = Vec::new();
+        for meta in &instruction.accounts {
+            if !unique_keys.contains(&meta.pubkey) {
+                unique_keys.push(meta.pubkey);
+            }
+        }
+        let pre: Vec<AccountState> = unique_keys
+            .iter()
+            .map(|key| accounts.get(key).cloned().unwrap_or_default())
+            .collect();
+
+        let mut input = SerializedInput::new(instruction, &unique_keys, &pre);
+
+        CONTEXT.with(|context| {
+            let mut context = context.borrow_mut();
+            context.clock = self.clock.clone();
+            context.program_stack = vec![instruction.program_id];
+            context.return_data = None;
+            context.logs.clear();
+            context.log_data.clear();
+        });
+
+        let result = unsafe {
+            let (program_id, account_infos, data) = deserialize(input.as_mut_ptr());
+            if *program_id == audio_token_platform::ID {
+                audio_token_platform::entry(program_id, &account_infos, data)
+            } else {
+                process_builtin(program_id, &account_infos, data)
+            }
+        };
+
+        CONTEXT.with(|context| {
+            let mut context = context.borrow_mut();
+            self.logs.append(&mut context.logs);
+            self.log_data.append(&mut context.log_data);
+        });
+
+        result.map_err(TxError::Program)?;
+
+        let post = input.read_accounts(unique_keys.len());
+
+        let pre_total: u128 = pre.iter().map(|account| account.lamports as u128).sum();
+        let post_total: u128 = post.iter().map(|account| account.lamports as u128).sum();
+        if pre_total != post_total {
+            return Err(TxError::UnbalancedInstruction);
+        }
+
+        let rent = Rent::default();
+        for (index, key) in unique_keys.iter().enumerate() {
+            let writable = instruction
+                .accounts
+                .iter()
+                .any(|meta| meta.pubkey == *key && meta.is_writable);
+            if !writable && post[index] != pre[index] {
+                return Err(TxError::ReadonlyModified(*key));
+            }
+            let account = &post[index];
+            if account.lamports > 0
+                && !account.data.is_empty()
+                && !rent.is_exempt(account.lamports, account.data.len())
+            {
+                return Err(TxError::InsufficientFundsForRent(*key));
+            }
+        }
+
+        for (key, account) in unique_keys.into_iter().zip(post) {
+            accounts.insert(key, account);
+        }
+        Ok(())
+    }
+}
+
+pub fn program_data_address(program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::ID).0
+}
+
+pub fn metadata_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"metadata", mpl_token_metadata::ID.as_ref(), mint.as_ref()],
+        &mpl_token_metadata::ID,
+    )
+    .0
+}
+
+// ============================================================================
+// INPUT SERIALIZATION
+// ============================================================================
+
+/// Input buffer in the aligned BPF loader layout. Backed by `u64`s so every
+/// field `deserialize` reads through a pointer cast is properly aligned.
+struct SerializedInput {
+    buffer: Vec<u64>,
+    account_offsets: Vec<usize>,
+}
+
+impl SerializedInput {
+    fn new(instruction: &Instruction, unique_keys: &[Pubkey], accounts: &[AccountState]) -> Self {
+        let mut bytes: Vec<u8> = Vec::new();
+        let mut account_offsets = vec![0; unique_keys.len()];
+
+        bytes.extend_from_slice(&(instruction.accounts.len() as u64).to_le_bytes());
+        for (position, meta) in instruction.accounts.iter().enumerate() {
+            if let Some(first) = instruction.accounts[..position]
+                .iter()
+                .position(|earlier| earlier.pubkey == meta.pubkey)
+            {
+                bytes.push(first as u8);
+                bytes.extend_from_slice(&[0u8; 7]);
+                continue;
+            }
+
+            let index = unique_keys
+                .iter()
+                .position(|key| *key == meta.pubkey)
+                .unwrap();
+            let account = &accounts[index];
+
+            // Writable/signer privileges are the union of every occurrence.
+            let is_signer = instruction
+                .accounts
+                .iter()
+                .any(|other| other.pubkey == meta.pubkey && other.is_signer);
+            let is_writable = instruction
+                .accounts
+                .iter()
+                .any(|other| other.pubkey == meta.pubkey && other.is_writable);
+
+            bytes.push(NON_DUP_MARKER);
+            bytes.push(is_signer as u8);
+            bytes.push(is_writable as u8);
+            bytes.push(account.executable as u8);
+            bytes.extend_from_slice(&[0u8; 4]);
+            account_offsets[index] = bytes.len();
+            bytes.extend_from_slice(meta.pubkey.as_ref());
+            bytes.extend_from_slice(account.owner.as_ref());
+            bytes.extend_from_slice(&account.lamports.to_le_bytes());
+            bytes.extend_from_slice(&(account.data.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(&account.data);
+            bytes.resize(bytes.len() + MAX_PERMITTED_DATA_INCREASE, 0);
+            bytes.resize(bytes.len().next_multiple_of(8), 0);
+            bytes.extend_from_slice(&u64::MAX.to_le_bytes()); // rent epoch
+        }
+
+        bytes.extend_from_slice(&(instruction.data.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(&instruction.data);
+        bytes.extend_from_slice(instruction.program_id.as_ref());
+        bytes.resize(bytes.len().next_multiple_of(8), 0);
+
+        let buffer = bytes
+            .chunks_exact(8)
+            .map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap()))
+            .collect();
+
+        Self {
+            buffer,
+            account_offsets,
+        }
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut u8 {
+        self.buffer.as_mut_ptr() as *mut u8
+    }
+
+    fn bytes(&self) -> &[u8] {
+        unsafe {
+            std::slice::from_raw_parts(self.buffer.as_ptr() as *const u8, self.buffer.len() * 8)
+        }
+    }
+
+    fn read_accounts(&self, count: usize) -> Vec<AccountState> {
+        let bytes = self.bytes();
+        let read_u64 =
+            |offset: usize| u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap());
+
+        (0..count)
+            .map(|index| {
+                let offset = self.account_offsets[index];
+                let executable = bytes[offset - 5] != 0;
+                let owner_offset = offset + size_of::<Pubkey>();
+                let lamports_offset = owner_offset + size_of::<Pubkey>();
+                let data_len = read_u64(lamports_offset + 8) as usize;
+                let data_offset = lamports_offset + 16;
+                AccountState {
+                    lamports: read_u64(lamports_offset),
+                    data: bytes[data_offset..data_offset + data_len].to_vec(),
+                    owner: Pubkey::try_from(&bytes[owner_offset..lamports_offset]).unwrap(),
+                    executable,
+                }
+            })
+            .collect()
+    }
+}
+
+// ============================================================================
+// SYSCALL STUBS
+// ============================================================================
+
+struct Stubs;
+
+impl SyscallStubs for Stubs {
+    fn sol_log(&self, message: &str) {
+        CONTEXT.with(|context| context.borrow_mut().logs.push(message.to_string()));
+    }
+
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        let encoded: Vec<String> = fields
+            .iter()
+            .map(|field| field.iter().map(|byte| format!("{byte:02x}")).collect())
+            .collect();
+        self.sol_log(&format!("Program data: {}", encoded.join(" ")));
+        CONTEXT.with(|context| context.borrow_mut().log_data.push(fields.concat()));
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let caller = CONTEXT.with(|context| *context.borrow().program_stack.last().unwrap());
+        let pda_signers = signers_seeds
+            .iter()
+            .map(|seeds| Pubkey::create_program_address(seeds, &caller))
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|_| ProgramError::InvalidSeeds)?;
+
+        let mut callee_infos = Vec::with_capacity(instruction.accounts.len());
+        for meta in &instruction.accounts {
+            let info = account_infos
+                .iter()
+                .find(|info| *info.key == meta.pubkey)
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+            if meta.is_signer && !info.is_signer && !pda_signers.contains(&meta.pubkey) {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if meta.is_writable && !info.is_writable {
+                return Err(ProgramError::InvalidArgument);
+            }
+            callee_infos.push(AccountInfo {
+                is_signer: meta.is_signer,
+                is_writable: meta.is_writable,
+                ..info.clone()
+            });
+        }
+
+        CONTEXT.with(|context| {
+            context
+                .borrow_mut()
+                .program_stack
+                .push(instruction.program_id)
+        });
+        let result = process_builtin(&instruction.program_id, &callee_infos, &instruction.data);
+        CONTEXT.with(|context| context.borrow_mut().program_stack.pop());
+        result
+    }
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        let clock = CONTEXT.with(|context| context.borrow().clock.clone());
+        unsafe { *(var_addr as *mut Clock) = clock };
+        0
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        0
+    }
+
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        CONTEXT.with(|context| context.borrow().return_data.clone())
+    }
+
+    fn sol_set_return_data(&self, data: &[u8]) {
+        CONTEXT.with(|context| {
+            let mut context = context.borrow_mut();
+            let program_id = *context.program_stack.last().unwrap();
+            context.return_data = Some((program_id, data.to_vec()));
+        });
+    }
+}
+
+// ============================================================================
+// BUILTIN PROGRAMS
+// ============================================================================
+
+fn process_builtin(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    if *program_id == system_program::ID {
+        process_system(accounts, data)
+    } else if *program_id == spl_token::ID {
+        spl_token::processor::Processor::process(program_id, accounts, data)
+    } else if *program_id == spl_associated_token_account::ID {
+        spl_associated_token_account::processor::process_instruction(program_id, accounts, data)
+    } else if *program_id == mpl_token_metadata::ID {
+        process_metadata(accounts, data)
+    } else {
+        Err(ProgramError::IncorrectProgramId)
+    }
+}
+
+fn debit(from: &AccountInfo, to: &AccountInfo, lamports: u64) -> ProgramResult {
+    let mut from_lamports = from.try_borrow_mut_lamports()?;
+    **from_lamports = from_lamports
+        .checked_sub(lamports)
+        .ok_or(ProgramError::Custom(1))?; // SystemError::ResultWithNegativeLamports
+    drop(from_lamports);
+    **to.try_borrow_mut_lamports()? += lamports;
+    Ok(())
+}
+
+fn process_system(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let instruction: SystemInstruction =
+        bincode::deserialize(data).map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    match instruction {
+        SystemInstruction::CreateAccount {
+            lamports,
+            space,
+            owner,
+        } => {
+            let (from, to) = (&accounts[0], &accounts[1]);
+            if !from.is_signer || !to.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if to.lamports() > 0 || !to.data_is_empty() || *to.owner != system_program::ID {
+                return Err(ProgramError::Custom(0)); // SystemError::AccountAlreadyInUse
+            }
+            debit(from, to, lamports)?;
+            to.realloc(space as usize, true)?;
+            to.assign(&owner);
+            Ok(())
+        }
+        SystemInstruction::Transfer { lamports } => {
+            let (from, to) = (&accounts[0], &accounts[1]);
+            if !from.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if !from.data_is_empty() || *from.owner != system_program::ID {
+                return Err(ProgramError::InvalidArgument);
+            }
+            debit(from, to, lamports)
+        }
+        SystemInstruction::Allocate { space } => {
+            let account = &accounts[0];
+            if !account.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if !account.data_is_empty() || *account.owner != system_program::ID {
+                return Err(ProgramError::Custom(0));
+            }
+            account.realloc(space as usize, true)
+        }
+        SystemInstruction::Assign { owner } => {
+            let account = &accounts[0];
+            if !account.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            account.assign(&owner);
+            Ok(())
+        }
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+fn process_metadata(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let (discriminator, args) = data
+        .split_first()
+        .ok_or(ProgramError::InvalidInstructionData)?;
+
+    match discriminator {
+        // CreateMetadataAccountV3
+        33 => {
+            let args = CreateMetadataAccountV3InstructionArgs::deserialize(&mut &args[..])?;
+            let (metadata, mint, mint_authority, payer, update_authority) = (
+                &accounts[0],
+                &accounts[1],
+                &accounts[2],
+                &accounts[3],
+                &accounts[4],
+            );
+
+            if *metadata.key != metadata_address(mint.key) {
+                return Err(ProgramError::InvalidSeeds);
+            }
+            let mint_state = spl_token::state::Mint::unpack(&mint.try_borrow_data()?)?;
+            if mint_state.mint_authority != Some(*mint_authority.key).into() {
+                return Err(ProgramError::IllegalOwner);
+            }
+            if !mint_authority.is_signer || !payer.is_signer || !update_authority.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+
+            let state = FakeMetadata {
+                update_authority: *update_authority.key,
+                mint: *mint.key,
+                data: args.data,
+                is_mutable: args.is_mutable,
+            }
+            .try_to_vec()?;
+            debit(
+                payer,
+                metadata,
+                Rent::default().minimum_balance(state.len()),
+            )?;
+            metadata.realloc(state.len(), true)?;
+            metadata.assign(&mpl_token_metadata::ID);
+            metadata.try_borrow_mut_data()?.copy_from_slice(&state);
+            Ok(())
+        }
+        // UpdateMetadataAccountV2
+        15 => {
+            let args = UpdateMetadataAccountV2InstructionArgs::deserialize(&mut &args[..])?;
+            let (metadata, update_authority) = (&accounts[0], &accounts[1]);
+
+            let mut state = FakeMetadata::deserialize(&mut &metadata.try_borrow_data()?[..])?;
+            if !update_authority.is_signer || state.update_authority != *update_authority.key {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if !state.is_mutable {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            if let Some(data) = args.data {
+                state.data = data;
+            }
+            if let Some(new_update_authority) = args.new_update_authority {
+                state.update_authority = new_update_authority;
+            }
+            if let Some(is_mutable) = args.is_mutable {
+                state.is_mutable = is_mutable;
+            }
+
+            let state = state.try_to_vec()?;
+            let lamports = Rent::default().minimum_balance(state.len());
+            if metadata.lamports() < lamports {
+                return Err(ProgramError::AccountNotRentExempt);
+            }
+            metadata.realloc(state.len(), false)?;
+            metadata.try_borrow_mut_data()?.copy_from_slice(&state);
+            Ok(())
+        }
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
@@ -0,0 +1,143 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address;
+use audio_token_platform::{
+    CreatorFeeVault, CreatorFeesClaimed, ErrorCode, TokenBought, TokenSold,
+    UpdatePlatformConfigParams, MAX_CREATOR_FEE_BPS,
+};
+use common::*;
+
+const CREATOR_FEE_BPS: u64 = 100;
+
+fn fixture_with_royalties() -> Fixture {
+    let mut fixture = Fixture::new();
+    fixture.update_platform_config(UpdatePlatformConfigParams {
+        max_creator_fee_bps: Some(CREATOR_FEE_BPS),
+        ..Default::default()
+    });
+    fixture
+}
+
+fn creator_fee_vault(fixture: &Fixture, mint: &Pubkey) -> CreatorFeeVault {
+    fixture.svm.anchor_account(&creator_fee_vault_address(mint))
+}
+
+fn claim(
+    fixture: &mut Fixture,
+    creator: &Pubkey,
+    mint: &Pubkey,
+) -> std::result::Result<(), TxError> {
+    fixture
+        .svm
+        .send(&[claim_creator_fees_ix(creator, mint)], &[creator])
+}
+
+#[test]
+fn creator_fee_is_bounded_by_the_platform_max() {
+    let mut fixture = fixture_with_royalties();
+    let creator = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+    let mint = Pubkey::new_unique();
+    let ix = create_audio_token_ix(
+        &creator,
+        &mint,
+        &fixture.treasury,
+        TOTAL_SUPPLY,
+        CURVE_PARAMS,
+        CREATOR_FEE_BPS + 1,
+    );
+    assert_error(
+        fixture.svm.send(&[ix], &[&creator, &mint]),
+        ErrorCode::InvalidFeeConfig,
+    );
+
+    let admin = fixture.admin;
+    let result = fixture.svm.send(
+        &[update_platform_config_ix(
+            &admin,
+            UpdatePlatformConfigParams {
+                max_creator_fee_bps: Some(MAX_CREATOR_FEE_BPS + 1),
+                ..Default::default()
+            },
+        )],
+        &[&admin],
+    );
+    assert_error(result, ErrorCode::InvalidFeeConfig);
+}
+
+#[test]
+fn trades_accrue_the_royalty_to_the_creator_fee_vault() {
+    let mut fixture = fixture_with_royalties();
+    let (creator, mint) = fixture.create_token_with_royalty(CREATOR_FEE_BPS);
+    assert_eq!(fixture.token_config(&mint).creator_fee_bps, CREATOR_FEE_BPS);
+    let vault = creator_fee_vault(&fixture, &mint);
+    assert_eq!(vault.creator, creator);
+    assert_eq!(vault.accrued_fees, 0);
+
+    let trader = fixture.funded_wallet(LAMPORTS_PER_SOL);
+    let treasury_before = fixture.svm.lamports(&fixture.treasury);
+    let vault_before = fixture.svm.lamports(&creator_fee_vault_address(&mint));
+    let sol_amount = LAMPORTS_PER_SOL / 50;
+    fixture.buy(&trader, &mint, sol_amount).unwrap();
+
+    let bought = fixture.svm.events::<TokenBought>().pop().unwrap();
+    assert_eq!(
+        bought.creator_royalty,
+        sol_amount * CREATOR_FEE_BPS / 10_000
+    );
+    // The royalty comes on top of the full trade fee
+    assert!(bought.fee >= (sol_amount * TRADE_FEE_BPS).div_ceil(10_000));
+    assert_eq!(
+        fixture.svm.lamports(&fixture.treasury) - treasury_before,
+        bought.fee
+    );
+
+    let balance = fixture
+        .svm
+        .token_balance(&get_associated_token_address(&trader, &mint));
+    fixture.sell(&trader, &mint, balance).unwrap();
+    let sold = fixture.svm.events::<TokenSold>().pop().unwrap();
+    assert!(sold.creator_royalty > 0);
+
+    let accrued = bought.creator_royalty + sold.creator_royalty;
+    assert_eq!(creator_fee_vault(&fixture, &mint).accrued_fees, accrued);
+    assert_eq!(
+        fixture.svm.lamports(&creator_fee_vault_address(&mint)) - vault_before,
+        accrued
+    );
+}
+
+#[test]
+fn creator_claims_accrued_fees_once() {
+    let mut fixture = fixture_with_royalties();
+    let (creator, mint) = fixture.create_token_with_royalty(CREATOR_FEE_BPS);
+    let trader = fixture.funded_wallet(LAMPORTS_PER_SOL);
+    fixture.buy(&trader, &mint, LAMPORTS_PER_SOL / 50).unwrap();
+    let accrued = creator_fee_vault(&fixture, &mint).accrued_fees;
+
+    let before = fixture.svm.lamports(&creator);
+    claim(&mut fixture, &creator, &mint).unwrap();
+
+    let event = fixture.svm.events::<CreatorFeesClaimed>().pop().unwrap();
+    assert_eq!(event.amount, accrued);
+    assert_eq!(event.claimed_fees, accrued);
+    assert_eq!(fixture.svm.lamports(&creator) - before, accrued);
+    assert_error(
+        claim(&mut fixture, &creator, &mint),
+        ErrorCode::NothingToClaim,
+    );
+}
+
+#[test]
+fn only_the_creator_can_claim() {
+    let mut fixture = fixture_with_royalties();
+    let (_, mint) = fixture.create_token_with_royalty(CREATOR_FEE_BPS);
+    let trader = fixture.funded_wallet(LAMPORTS_PER_SOL);
+    fixture.buy(&trader, &mint, LAMPORTS_PER_SOL / 50).unwrap();
+
+    let attacker = fixture.funded_wallet(LAMPORTS_PER_SOL);
+    assert_error(
+        claim(&mut fixture, &attacker, &mint),
+        ErrorCode::Unauthorized,
+    );
+}
@@ -0,0 +1,124 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address;
+use audio_token_platform::{CurveParams, ErrorCode};
+use common::*;
+
+fn create_with(
+    fixture: &mut Fixture,
+    curve_params: CurveParams,
+) -> (Pubkey, std::result::Result<(), TxError>) {
+    let creator = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+    let mint = Pubkey::new_unique();
+    let ix = create_audio_token_ix(
+        &creator,
+        &mint,
+        &fixture.treasury,
+        TOTAL_SUPPLY,
+        curve_params,
+        0,
+    );
+    (mint, fixture.svm.send(&[ix], &[&creator, &mint]))
+}
+
+#[test]
+fn curve_starts_from_creator_params() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+
+    let token_config = fixture.token_config(&mint);
+    assert_eq!(
+        token_config.virtual_sol_reserves,
+        CURVE_PARAMS.virtual_sol_reserves
+    );
+    assert_eq!(
+        token_config.virtual_token_reserves,
+        CURVE_PARAMS.virtual_token_reserves
+    );
+    assert_eq!(token_config.real_token_reserves, TOTAL_SUPPLY / 10);
+    assert_eq!(
+        fixture.svm.token_balance(&reserve_address(&mint)),
+        TOTAL_SUPPLY / 10
+    );
+}
+
+#[test]
+fn buys_are_priced_on_virtual_reserves() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let trader = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+
+    let sol_amount = LAMPORTS_PER_SOL / 100;
+    fixture.buy(&trader, &mint, sol_amount).unwrap();
+
+    let sol_to_curve = sol_amount - sol_amount * TRADE_FEE_BPS / 10_000;
+    // The remaining virtual token reserve is rounded up, in favour of the pool
+    let k = CURVE_PARAMS.virtual_sol_reserves as u128 * CURVE_PARAMS.virtual_token_reserves as u128;
+    let remaining = k.div_ceil((CURVE_PARAMS.virtual_sol_reserves + sol_to_curve) as u128);
+    let tokens_out = CURVE_PARAMS.virtual_token_reserves - remaining as u64;
+    assert_eq!(
+        fixture
+            .svm
+            .token_balance(&get_associated_token_address(&trader, &mint)),
+        tokens_out
+    );
+
+    let token_config = fixture.token_config(&mint);
+    assert_eq!(
+        token_config.virtual_token_reserves,
+        CURVE_PARAMS.virtual_token_reserves - tokens_out
+    );
+    assert_eq!(
+        token_config.real_token_reserves,
+        TOTAL_SUPPLY / 10 - tokens_out
+    );
+}
+
+#[test]
+fn create_rejects_zero_virtual_sol() {
+    let mut fixture = Fixture::new();
+
+    let (_, result) = create_with(
+        &mut fixture,
+        CurveParams {
+            virtual_sol_reserves: 0,
+            ..CURVE_PARAMS
+        },
+    );
+
+    assert_error(result, ErrorCode::InvalidCurveParams);
+}
+
+#[test]
+fn create_rejects_virtual_tokens_below_curve_supply() {
+    let mut fixture = Fixture::new();
+
+    let (_, result) = create_with(
+        &mut fixture,
+        CurveParams {
+            virtual_token_reserves: TOTAL_SUPPLY / 10 - 1,
+            ..CURVE_PARAMS
+        },
+    );
+
+    assert_error(result, ErrorCode::InvalidCurveParams);
+}
+
+#[test]
+fn create_rejects_curve_that_sells_out_before_graduation() {
+    let mut fixture = Fixture::new();
+
+    // At the graduation threshold almost the whole virtual token reserve is
+    // sold, which is more than the curve actually holds.
+    let (mint, result) = create_with(
+        &mut fixture,
+        CurveParams {
+            virtual_token_reserves: 2 * (TOTAL_SUPPLY / 10),
+            ..CURVE_PARAMS
+        },
+    );
+
+    assert_error(result, ErrorCode::InvalidCurveParams);
+    assert!(fixture.svm.account(&token_config_address(&mint)).is_none());
+}
@@ -0,0 +1,146 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address;
+use audio_token_platform::{CurveParams, CurveType, TokenCreated};
+use common::*;
+
+// A higher starting price than `CURVE_PARAMS`, so the faster-rising curves
+// still hold tokens at the graduation threshold.
+const LINEAR: CurveParams = CurveParams {
+    curve_type: CurveType::Linear,
+    virtual_sol_reserves: LAMPORTS_PER_SOL / 10,
+    virtual_token_reserves: CURVE_PARAMS.virtual_token_reserves,
+};
+const EXPONENTIAL: CurveParams = CurveParams {
+    curve_type: CurveType::Exponential,
+    ..LINEAR
+};
+
+fn sol_to_curve(sol_amount: u64) -> u64 {
+    sol_amount - sol_amount * TRADE_FEE_BPS / 10_000
+}
+
+fn buy_and_balance(fixture: &mut Fixture, trader: &Pubkey, mint: &Pubkey, sol_amount: u64) -> u64 {
+    let ata = get_associated_token_address(trader, mint);
+    let before = fixture
+        .svm
+        .account(&ata)
+        .map_or(0, |_| fixture.svm.token_balance(&ata));
+    fixture.buy(trader, mint, sol_amount).unwrap();
+    fixture.svm.token_balance(&ata) - before
+}
+
+fn assert_close(actual: u64, expected: f64) {
+    let error = (actual as f64 - expected).abs() / expected;
+    assert!(error < 1e-9, "{actual} is not close to {expected}");
+}
+
+#[test]
+fn curve_type_is_stored_and_emitted() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token_with(EXPONENTIAL);
+
+    let token_config = fixture.token_config(&mint);
+    assert_eq!(token_config.curve_type, CurveType::Exponential);
+    assert_eq!(
+        token_config.initial_virtual_sol_reserves,
+        EXPONENTIAL.virtual_sol_reserves
+    );
+    assert_eq!(
+        token_config.initial_virtual_token_reserves,
+        EXPONENTIAL.virtual_token_reserves
+    );
+    assert_eq!(
+        fixture.svm.events::<TokenCreated>()[0].curve_type,
+        CurveType::Exponential
+    );
+}
+
+#[test]
+fn linear_curve_prices_on_tokens_sold() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token_with(LINEAR);
+    let trader = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+
+    let sol_amount = LAMPORTS_PER_SOL / 100;
+    let first = buy_and_balance(&mut fixture, &trader, &mint, sol_amount);
+    let second = buy_and_balance(&mut fixture, &trader, &mint, sol_amount);
+
+    // sol0 * (u1^2 - u0^2) / (2 * scale^2) with u = scale + tokens_sold
+    let sol0 = LINEAR.virtual_sol_reserves as f64;
+    let scale = LINEAR.virtual_token_reserves as f64;
+    let u1 = (scale * scale + 2.0 * scale * scale * sol_to_curve(sol_amount) as f64 / sol0).sqrt();
+    assert_close(first, u1 - scale);
+    assert!(second < first);
+    assert_eq!(fixture.token_config(&mint).tokens_sold, first + second);
+}
+
+#[test]
+fn exponential_curve_prices_on_tokens_sold() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token_with(EXPONENTIAL);
+    let trader = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+
+    let sol_amount = LAMPORTS_PER_SOL / 100;
+    let first = buy_and_balance(&mut fixture, &trader, &mint, sol_amount);
+    let second = buy_and_balance(&mut fixture, &trader, &mint, sol_amount);
+
+    // sol0 * (e^(s1 / scale) - 1), solved for s1
+    let sol0 = EXPONENTIAL.virtual_sol_reserves as f64;
+    let scale = EXPONENTIAL.virtual_token_reserves as f64;
+    assert_close(
+        first,
+        scale * (1.0 + sol_to_curve(sol_amount) as f64 / sol0).ln(),
+    );
+    assert!(second < first);
+}
+
+#[test]
+fn selling_back_never_pays_out_more_than_was_paid_in() {
+    for params in [CURVE_PARAMS, LINEAR, EXPONENTIAL] {
+        let mut fixture = Fixture::new();
+        let (_, mint) = fixture.create_token_with(params);
+        let trader = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+
+        let sol_amount = LAMPORTS_PER_SOL / 20;
+        let tokens = buy_and_balance(&mut fixture, &trader, &mint, sol_amount);
+        let before = fixture.svm.lamports(&trader);
+        fixture.sell(&trader, &mint, tokens / 2).unwrap();
+        let received = fixture.svm.lamports(&trader) - before;
+
+        // Selling walks back down the curve, so half the tokens return the
+        // more expensive half of the buy
+        assert!(received > sol_amount / 2, "{:?}", params.curve_type);
+        assert!(
+            received < sol_to_curve(sol_amount),
+            "{:?}",
+            params.curve_type
+        );
+        assert_eq!(fixture.token_config(&mint).tokens_sold, tokens - tokens / 2);
+    }
+}
+
+#[test]
+fn full_round_trip_never_profits() {
+    for params in [CURVE_PARAMS, LINEAR, EXPONENTIAL] {
+        let mut fixture = Fixture::new();
+        let (_, mint) = fixture.create_token_with(params);
+        let trader = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+
+        let sol_amount = LAMPORTS_PER_SOL / 20;
+        let tokens = buy_and_balance(&mut fixture, &trader, &mint, sol_amount);
+        let before = fixture.svm.lamports(&trader);
+        fixture.sell(&trader, &mint, tokens).unwrap();
+        let received = fixture.svm.lamports(&trader) - before;
+
+        assert!(
+            received <= sol_to_curve(sol_amount),
+            "{:?}",
+            params.curve_type
+        );
+        let token_config = fixture.token_config(&mint);
+        assert_eq!(token_config.tokens_sold, 0);
+        assert_eq!(token_config.real_token_reserves, TOTAL_SUPPLY / 10);
+    }
+}
@@ -0,0 +1,104 @@
+mod common;
+
+use anchor_spl::associated_token::get_associated_token_address;
+use audio_token_platform::ErrorCode;
+use common::*;
+
+#[test]
+fn trade_before_deadline_succeeds() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let trader = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+    let now = fixture.svm.clock().unix_timestamp;
+
+    let ix = buy_tokens_ix(
+        &trader,
+        &mint,
+        &fixture.treasury,
+        LAMPORTS_PER_SOL / 100,
+        0,
+        Some(now),
+    );
+    fixture.svm.send(&[ix], &[&trader]).unwrap();
+}
+
+#[test]
+fn buy_after_deadline_fails() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let trader = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+    let deadline = fixture.svm.clock().unix_timestamp + 60;
+    fixture.svm.advance_time(61);
+
+    let ix = buy_tokens_ix(
+        &trader,
+        &mint,
+        &fixture.treasury,
+        LAMPORTS_PER_SOL / 100,
+        0,
+        Some(deadline),
+    );
+    let result = fixture.svm.send(&[ix], &[&trader]);
+
+    assert_error(result, ErrorCode::DeadlineExceeded);
+}
+
+#[test]
+fn sell_after_deadline_fails() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let trader = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+    fixture.buy(&trader, &mint, LAMPORTS_PER_SOL / 100).unwrap();
+    let balance = fixture
+        .svm
+        .token_balance(&get_associated_token_address(&trader, &mint));
+    let deadline = fixture.svm.clock().unix_timestamp;
+    fixture.svm.advance_time(1);
+
+    let ix = sell_tokens_ix(
+        &trader,
+        &mint,
+        &fixture.treasury,
+        balance,
+        0,
+        Some(deadline),
+    );
+    let result = fixture.svm.send(&[ix], &[&trader]);
+
+    assert_error(result, ErrorCode::DeadlineExceeded);
+}
+
+#[test]
+fn exact_output_trades_check_the_deadline() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let trader = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+    fixture.buy(&trader, &mint, LAMPORTS_PER_SOL / 100).unwrap();
+    let deadline = fixture.svm.clock().unix_timestamp;
+    fixture.svm.advance_time(1);
+
+    let buy = buy_exact_tokens_out_ix(
+        &trader,
+        &mint,
+        &fixture.treasury,
+        1_000_000_000,
+        u64::MAX,
+        Some(deadline),
+    );
+    assert_error(
+        fixture.svm.send(&[buy], &[&trader]),
+        ErrorCode::DeadlineExceeded,
+    );
+    let sell = sell_for_exact_sol_ix(
+        &trader,
+        &mint,
+        &fixture.treasury,
+        1_000,
+        u64::MAX,
+        Some(deadline),
+    );
+    assert_error(
+        fixture.svm.send(&[sell], &[&trader]),
+        ErrorCode::DeadlineExceeded,
+    );
+}
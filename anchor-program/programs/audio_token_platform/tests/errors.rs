@@ -0,0 +1,155 @@
+//! Failure paths for the error codes not already covered by the
+//! instruction-specific test files.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address;
+use audio_token_platform::{CurveParams, CurveType, ErrorCode, UpdatePlatformConfigParams};
+use common::*;
+
+const LINEAR: CurveParams = CurveParams {
+    curve_type: CurveType::Linear,
+    virtual_sol_reserves: LAMPORTS_PER_SOL / 10,
+    virtual_token_reserves: CURVE_PARAMS.virtual_token_reserves,
+};
+
+fn update_metadata(
+    fixture: &mut Fixture,
+    creator: &Pubkey,
+    mint: &Pubkey,
+    symbol: &str,
+    metadata_uri: &str,
+) -> std::result::Result<(), TxError> {
+    let ix = update_token_metadata_ix(creator, mint, "Noiz Sound", symbol, metadata_uri);
+    fixture.svm.send(&[ix], &[creator])
+}
+
+#[test]
+fn zero_amounts_are_rejected() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let trader = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+
+    assert_error(fixture.buy(&trader, &mint, 0), ErrorCode::InvalidAmount);
+    fixture.buy(&trader, &mint, LAMPORTS_PER_SOL / 100).unwrap();
+    assert_error(fixture.sell(&trader, &mint, 0), ErrorCode::InvalidAmount);
+
+    let add = add_liquidity_ix(&trader, &mint, 0, 1, 0);
+    assert_error(
+        fixture.svm.send(&[add], &[&trader]),
+        ErrorCode::InvalidAmount,
+    );
+    let tokens = fixture
+        .svm
+        .token_balance(&get_associated_token_address(&trader, &mint));
+    let add = add_liquidity_ix(&trader, &mint, LAMPORTS_PER_SOL, tokens, 0);
+    fixture.svm.send(&[add], &[&trader]).unwrap();
+    let remove = remove_liquidity_ix(&trader, &mint, 0, 0, 0);
+    assert_error(
+        fixture.svm.send(&[remove], &[&trader]),
+        ErrorCode::InvalidAmount,
+    );
+}
+
+#[test]
+fn oversized_buy_overflows() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token_with(LINEAR);
+    let trader = fixture.funded_wallet(LAMPORTS_PER_SOL);
+
+    assert_error(
+        fixture.buy(&trader, &mint, u64::MAX),
+        ErrorCode::MathOverflow,
+    );
+}
+
+#[test]
+fn creation_can_be_paused() {
+    let mut fixture = Fixture::new();
+    fixture.update_platform_config(UpdatePlatformConfigParams {
+        creation_paused: Some(true),
+        ..Default::default()
+    });
+    let creator = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+    let mint = Pubkey::new_unique();
+
+    let ix = create_audio_token_ix(
+        &creator,
+        &mint,
+        &fixture.treasury,
+        TOTAL_SUPPLY,
+        CURVE_PARAMS,
+        0,
+    );
+
+    assert_error(
+        fixture.svm.send(&[ix], &[&creator, &mint]),
+        ErrorCode::CreationPaused,
+    );
+    assert!(fixture.svm.account(&mint).is_none());
+}
+
+#[test]
+fn invalid_symbol_is_rejected() {
+    let mut fixture = Fixture::new();
+    let (creator, mint) = fixture.create_token();
+    let uri = fixture.token_config(&mint).metadata_uri;
+
+    for symbol in ["", "NO IZ", "$NOIZ"] {
+        assert_error(
+            update_metadata(&mut fixture, &creator, &mint, symbol, &uri),
+            ErrorCode::InvalidSymbol,
+        );
+    }
+}
+
+#[test]
+fn invalid_uri_is_rejected() {
+    let mut fixture = Fixture::new();
+    let (creator, mint) = fixture.create_token();
+
+    for uri in ["", "http://example.com/noiz.json", "ipfs://"] {
+        assert_error(
+            update_metadata(&mut fixture, &creator, &mint, "NOIZ", uri),
+            ErrorCode::InvalidUri,
+        );
+    }
+}
+
+#[test]
+fn remove_liquidity_checks_slippage() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let provider = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+    fixture
+        .buy(&provider, &mint, LAMPORTS_PER_SOL / 100)
+        .unwrap();
+    let tokens = fixture
+        .svm
+        .token_balance(&get_associated_token_address(&provider, &mint));
+    fixture
+        .svm
+        .send(
+            &[add_liquidity_ix(
+                &provider,
+                &mint,
+                LAMPORTS_PER_SOL,
+                tokens,
+                0,
+            )],
+            &[&provider],
+        )
+        .unwrap();
+
+    let remove = remove_liquidity_ix(&provider, &mint, 1_000, u64::MAX, 0);
+    assert_error(
+        fixture.svm.send(&[remove], &[&provider]),
+        ErrorCode::SlippageExceeded,
+    );
+    let remove = remove_liquidity_ix(&provider, &mint, 1_000, 0, u64::MAX);
+    assert_error(
+        fixture.svm.send(&[remove], &[&provider]),
+        ErrorCode::SlippageExceeded,
+    );
+}
@@ -0,0 +1,277 @@
+mod common;
+
+use anchor_spl::associated_token::get_associated_token_address;
+use audio_token_platform::{
+    AdminProposed, AdminTransferred, LiquidityAdded, LiquidityRemoved, PlatformConfigUpdated,
+    TokenBought, TokenCreated, TokenGraduated, TokenSold, TradingHaltChanged,
+    UpdatePlatformConfigParams,
+};
+use common::*;
+
+#[test]
+fn create_audio_token_emits_token_created() {
+    let mut fixture = Fixture::new();
+    let (creator, mint) = fixture.create_token();
+
+    let events = fixture.svm.events::<TokenCreated>();
+    assert_eq!(events.len(), 1);
+    let event = &events[0];
+    let token_config = fixture.token_config(&mint);
+    assert_eq!(event.mint, mint);
+    assert_eq!(event.creator, creator);
+    assert_eq!(event.name, token_config.name);
+    assert_eq!(event.symbol, token_config.symbol);
+    assert_eq!(event.metadata_uri, token_config.metadata_uri);
+    assert_eq!(event.total_supply, TOTAL_SUPPLY);
+    assert_eq!(
+        event.virtual_sol_reserves,
+        token_config.virtual_sol_reserves
+    );
+    assert_eq!(
+        event.virtual_token_reserves,
+        token_config.virtual_token_reserves
+    );
+    assert_eq!(event.real_token_reserves, token_config.real_token_reserves);
+    assert_eq!(event.timestamp, fixture.svm.clock().unix_timestamp);
+}
+
+#[test]
+fn trades_emit_post_trade_reserves() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let trader = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+    let trader_ata = get_associated_token_address(&trader, &mint);
+
+    let sol_amount = LAMPORTS_PER_SOL / 100;
+    fixture.buy(&trader, &mint, sol_amount).unwrap();
+
+    let bought = fixture.svm.events::<TokenBought>();
+    assert_eq!(bought.len(), 1);
+    let token_config = fixture.token_config(&mint);
+    assert_eq!(bought[0].mint, mint);
+    assert_eq!(bought[0].trader, trader);
+    assert_eq!(bought[0].sol_amount, sol_amount);
+    assert_eq!(
+        bought[0].token_amount,
+        fixture.svm.token_balance(&trader_ata)
+    );
+    assert_eq!(bought[0].fee, sol_amount * TRADE_FEE_BPS / 10_000);
+    assert_eq!(
+        bought[0].virtual_sol_reserves,
+        token_config.virtual_sol_reserves
+    );
+    assert_eq!(bought[0].real_sol_reserves, token_config.real_sol_reserves);
+    assert_eq!(
+        bought[0].virtual_token_reserves,
+        token_config.virtual_token_reserves
+    );
+    assert_eq!(
+        bought[0].real_token_reserves,
+        token_config.real_token_reserves
+    );
+
+    let token_amount = fixture.svm.token_balance(&trader_ata) / 2;
+    let treasury_before = fixture.svm.lamports(&fixture.treasury);
+    fixture.sell(&trader, &mint, token_amount).unwrap();
+
+    let sold = fixture.svm.events::<TokenSold>();
+    assert_eq!(sold.len(), 1);
+    let token_config = fixture.token_config(&mint);
+    assert_eq!(sold[0].trader, trader);
+    assert_eq!(sold[0].token_amount, token_amount);
+    assert_eq!(
+        sold[0].fee,
+        fixture.svm.lamports(&fixture.treasury) - treasury_before
+    );
+    assert_eq!(
+        sold[0].virtual_sol_reserves,
+        token_config.virtual_sol_reserves
+    );
+    assert_eq!(sold[0].real_sol_reserves, token_config.real_sol_reserves);
+    assert_eq!(
+        sold[0].virtual_token_reserves,
+        token_config.virtual_token_reserves
+    );
+    assert_eq!(
+        sold[0].real_token_reserves,
+        token_config.real_token_reserves
+    );
+    assert!(fixture.svm.events::<TokenBought>().is_empty());
+}
+
+#[test]
+fn liquidity_changes_emit_events() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let provider = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+    fixture
+        .buy(&provider, &mint, LAMPORTS_PER_SOL / 100)
+        .unwrap();
+    let tokens = fixture
+        .svm
+        .token_balance(&get_associated_token_address(&provider, &mint));
+
+    let sol_amount = LAMPORTS_PER_SOL / 1000;
+    fixture
+        .svm
+        .send(
+            &[add_liquidity_ix(&provider, &mint, sol_amount, tokens, 0)],
+            &[&provider],
+        )
+        .unwrap();
+
+    let added = fixture.svm.events::<LiquidityAdded>();
+    assert_eq!(added.len(), 1);
+    let token_config = fixture.token_config(&mint);
+    assert_eq!(added[0].provider, provider);
+    assert_eq!(added[0].sol_amount, sol_amount);
+    assert_eq!(
+        added[0].token_amount,
+        tokens
+            - fixture
+                .svm
+                .token_balance(&get_associated_token_address(&provider, &mint))
+    );
+    assert_eq!(
+        added[0].virtual_sol_reserves,
+        token_config.virtual_sol_reserves
+    );
+    assert_eq!(added[0].real_sol_reserves, token_config.real_sol_reserves);
+    assert_eq!(
+        added[0].virtual_token_reserves,
+        token_config.virtual_token_reserves
+    );
+    assert_eq!(
+        added[0].real_token_reserves,
+        token_config.real_token_reserves
+    );
+
+    fixture
+        .svm
+        .send(
+            &[remove_liquidity_ix(&provider, &mint, added[0].shares, 0, 0)],
+            &[&provider],
+        )
+        .unwrap();
+
+    let removed = fixture.svm.events::<LiquidityRemoved>();
+    assert_eq!(removed.len(), 1);
+    let token_config = fixture.token_config(&mint);
+    assert_eq!(removed[0].lp_share, added[0].shares);
+    assert_eq!(removed[0].position_shares, 0);
+    assert_eq!(removed[0].liquidity, added[0].liquidity - added[0].shares);
+    assert_eq!(
+        removed[0].virtual_sol_reserves,
+        token_config.virtual_sol_reserves
+    );
+    assert_eq!(removed[0].real_sol_reserves, token_config.real_sol_reserves);
+    assert_eq!(
+        removed[0].virtual_token_reserves,
+        token_config.virtual_token_reserves
+    );
+    assert_eq!(
+        removed[0].real_token_reserves,
+        token_config.real_token_reserves
+    );
+}
+
+#[test]
+fn graduate_emits_token_graduated() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let trader = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+    fixture
+        .buy(&trader, &mint, GRADUATION_THRESHOLD * 101 / 100)
+        .unwrap();
+
+    let payer = fixture.funded_wallet(LAMPORTS_PER_SOL);
+    fixture
+        .svm
+        .send(&[graduate_ix(&payer, &mint)], &[&payer])
+        .unwrap();
+
+    let events = fixture.svm.events::<TokenGraduated>();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].mint, mint);
+    assert!(events[0].sol_amount > 0);
+    assert!(events[0].token_amount > 0);
+}
+
+#[test]
+fn initialize_platform_emits_platform_config_updated() {
+    let fixture = Fixture::new();
+
+    let events = fixture.svm.events::<PlatformConfigUpdated>();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].admin, fixture.admin);
+    assert_eq!(events[0].fee_recipient, fixture.treasury);
+    assert_eq!(events[0].trade_fee_bps, TRADE_FEE_BPS);
+    assert_eq!(events[0].creation_fee, CREATION_FEE);
+    assert_eq!(events[0].graduation_threshold, GRADUATION_THRESHOLD);
+}
+
+#[test]
+fn update_platform_config_emits_the_new_settings() {
+    let mut fixture = Fixture::new();
+    let fee_recipient = fixture.funded_wallet(LAMPORTS_PER_SOL);
+    fixture.update_platform_config(UpdatePlatformConfigParams {
+        trade_fee_bps: Some(50),
+        fee_recipient: Some(fee_recipient),
+        trading_paused: Some(true),
+        ..Default::default()
+    });
+
+    let events = fixture.svm.events::<PlatformConfigUpdated>();
+    assert_eq!(events.len(), 1);
+    let event = &events[0];
+    let platform_config = fixture.platform_config();
+    assert_eq!(event.fee_recipient, fee_recipient);
+    assert_eq!(event.trade_fee_bps, 50);
+    assert!(event.trading_paused);
+    assert_eq!(event.creation_paused, platform_config.creation_paused);
+    assert_eq!(event.creation_fee, platform_config.creation_fee);
+    assert_eq!(event.timestamp, fixture.svm.clock().unix_timestamp);
+}
+
+#[test]
+fn admin_transfer_emits_proposed_and_transferred() {
+    let mut fixture = Fixture::new();
+    let admin = fixture.admin;
+    let new_admin = fixture.funded_wallet(LAMPORTS_PER_SOL);
+
+    fixture
+        .svm
+        .send(&[propose_admin_ix(&admin, &new_admin)], &[&admin])
+        .unwrap();
+    let proposed = fixture.svm.events::<AdminProposed>();
+    assert_eq!(proposed.len(), 1);
+    assert_eq!(proposed[0].admin, admin);
+    assert_eq!(proposed[0].pending_admin, new_admin);
+
+    fixture
+        .svm
+        .send(&[accept_admin_ix(&new_admin)], &[&new_admin])
+        .unwrap();
+    let transferred = fixture.svm.events::<AdminTransferred>();
+    assert_eq!(transferred.len(), 1);
+    assert_eq!(transferred[0].previous_admin, admin);
+    assert_eq!(transferred[0].new_admin, new_admin);
+}
+
+#[test]
+fn set_trading_halted_emits_trading_halt_changed() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let admin = fixture.admin;
+
+    for halted in [true, false] {
+        fixture
+            .svm
+            .send(&[set_trading_halted_ix(&admin, &mint, halted)], &[&admin])
+            .unwrap();
+        let events = fixture.svm.events::<TradingHaltChanged>();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].mint, mint);
+        assert_eq!(events[0].halted, halted);
+    }
+}
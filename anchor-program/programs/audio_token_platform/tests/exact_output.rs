@@ -0,0 +1,177 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address;
+use audio_token_platform::{CurveParams, CurveType, ErrorCode, TokenBought, TokenSold};
+use common::*;
+
+const LINEAR: CurveParams = CurveParams {
+    curve_type: CurveType::Linear,
+    virtual_sol_reserves: LAMPORTS_PER_SOL / 10,
+    virtual_token_reserves: CURVE_PARAMS.virtual_token_reserves,
+};
+const EXPONENTIAL: CurveParams = CurveParams {
+    curve_type: CurveType::Exponential,
+    ..LINEAR
+};
+
+fn buy_exact(
+    fixture: &mut Fixture,
+    buyer: &Pubkey,
+    mint: &Pubkey,
+    token_amount: u64,
+    max_sol_in: u64,
+) -> std::result::Result<(), TxError> {
+    let ix = buy_exact_tokens_out_ix(
+        buyer,
+        mint,
+        &fixture.treasury,
+        token_amount,
+        max_sol_in,
+        None,
+    );
+    fixture.svm.send(&[ix], &[buyer])
+}
+
+fn sell_exact(
+    fixture: &mut Fixture,
+    seller: &Pubkey,
+    mint: &Pubkey,
+    sol_amount: u64,
+    max_tokens_in: u64,
+) -> std::result::Result<(), TxError> {
+    let ix = sell_for_exact_sol_ix(
+        seller,
+        mint,
+        &fixture.treasury,
+        sol_amount,
+        max_tokens_in,
+        None,
+    );
+    fixture.svm.send(&[ix], &[seller])
+}
+
+#[test]
+fn buy_exact_tokens_out_delivers_the_exact_amount() {
+    for params in [CURVE_PARAMS, LINEAR, EXPONENTIAL] {
+        let mut fixture = Fixture::new();
+        let (_, mint) = fixture.create_token_with(params);
+        let trader = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+
+        let token_amount = 1_000_000 * 1_000_000_000;
+        let before = fixture.svm.lamports(&trader);
+        buy_exact(&mut fixture, &trader, &mint, token_amount, LAMPORTS_PER_SOL).unwrap();
+
+        let ata = get_associated_token_address(&trader, &mint);
+        assert_eq!(fixture.svm.token_balance(&ata), token_amount);
+        let event = fixture.svm.events::<TokenBought>().pop().unwrap();
+        assert_eq!(event.token_amount, token_amount);
+        assert_eq!(
+            event.fee,
+            (event.sol_amount * TRADE_FEE_BPS).div_ceil(10_000)
+        );
+        // Rent for the new token account comes out of the trader's lamports too
+        assert!(before - fixture.svm.lamports(&trader) >= event.sol_amount);
+
+        let token_config = fixture.token_config(&mint);
+        assert_eq!(token_config.tokens_sold, token_amount);
+        assert_eq!(token_config.real_sol_reserves, event.sol_amount - event.fee);
+    }
+}
+
+#[test]
+fn buy_exact_tokens_out_costs_no_less_than_the_matching_buy() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let trader = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+
+    // Buying by SOL first, then pricing the same number of tokens from the
+    // same starting point, must not come out cheaper
+    let sol_amount = LAMPORTS_PER_SOL / 100;
+    fixture.buy(&trader, &mint, sol_amount).unwrap();
+    let tokens = fixture.token_config(&mint).tokens_sold;
+
+    let mut other = Fixture::new();
+    let (_, other_mint) = other.create_token();
+    let other_trader = other.funded_wallet(10 * LAMPORTS_PER_SOL);
+    buy_exact(&mut other, &other_trader, &other_mint, tokens, u64::MAX).unwrap();
+
+    let paid = other.svm.events::<TokenBought>().pop().unwrap().sol_amount;
+    assert!(paid <= sol_amount + 1, "{paid} vs {sol_amount}");
+    assert!(paid + 2 >= sol_amount, "{paid} vs {sol_amount}");
+}
+
+#[test]
+fn buy_exact_tokens_out_enforces_max_sol_in() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let trader = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+
+    let result = buy_exact(&mut fixture, &trader, &mint, 1_000_000 * 1_000_000_000, 1);
+
+    assert_error(result, ErrorCode::SlippageExceeded);
+}
+
+#[test]
+fn sell_for_exact_sol_pays_the_exact_amount() {
+    for params in [CURVE_PARAMS, LINEAR, EXPONENTIAL] {
+        let mut fixture = Fixture::new();
+        let (_, mint) = fixture.create_token_with(params);
+        let trader = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+        fixture.buy(&trader, &mint, LAMPORTS_PER_SOL / 20).unwrap();
+        let ata = get_associated_token_address(&trader, &mint);
+        let balance = fixture.svm.token_balance(&ata);
+
+        let sol_amount = LAMPORTS_PER_SOL / 50;
+        let real_before = fixture.token_config(&mint).real_sol_reserves;
+        let before = fixture.svm.lamports(&trader);
+        let treasury_before = fixture.svm.lamports(&fixture.treasury);
+        sell_exact(&mut fixture, &trader, &mint, sol_amount, balance).unwrap();
+
+        assert_eq!(fixture.svm.lamports(&trader) - before, sol_amount);
+        let event = fixture.svm.events::<TokenSold>().pop().unwrap();
+        assert_eq!(event.sol_amount, sol_amount);
+        assert_eq!(
+            fixture.svm.lamports(&fixture.treasury) - treasury_before,
+            event.fee
+        );
+        assert_eq!(
+            balance - fixture.svm.token_balance(&ata),
+            event.token_amount
+        );
+        assert_eq!(
+            fixture.token_config(&mint).real_sol_reserves,
+            real_before - sol_amount - event.fee
+        );
+    }
+}
+
+#[test]
+fn sell_for_exact_sol_enforces_max_tokens_in() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let trader = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+    fixture.buy(&trader, &mint, LAMPORTS_PER_SOL / 20).unwrap();
+
+    let result = sell_exact(&mut fixture, &trader, &mint, LAMPORTS_PER_SOL / 50, 1);
+
+    assert_error(result, ErrorCode::SlippageExceeded);
+}
+
+#[test]
+fn sell_for_exact_sol_cannot_exceed_real_reserves() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let trader = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+    fixture.buy(&trader, &mint, LAMPORTS_PER_SOL / 100).unwrap();
+
+    let result = sell_exact(
+        &mut fixture,
+        &trader,
+        &mint,
+        LAMPORTS_PER_SOL / 100,
+        u64::MAX,
+    );
+
+    assert_error(result, ErrorCode::InsufficientLiquidity);
+}
@@ -0,0 +1,145 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use audio_token_platform::{ErrorCode, UpdatePlatformConfigParams};
+use common::*;
+
+#[test]
+fn create_audio_token_rejects_foreign_fee_recipient() {
+    let mut fixture = Fixture::new();
+    let creator = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+    let attacker = fixture.funded_wallet(LAMPORTS_PER_SOL);
+    let mint = Pubkey::new_unique();
+
+    let result = fixture.svm.send(
+        &[create_audio_token_ix(
+            &creator,
+            &mint,
+            &attacker,
+            TOTAL_SUPPLY,
+            CURVE_PARAMS,
+            0,
+        )],
+        &[&creator, &mint],
+    );
+
+    assert_error(result, ErrorCode::InvalidFeeRecipient);
+    assert_eq!(fixture.svm.lamports(&attacker), LAMPORTS_PER_SOL);
+    assert!(fixture.svm.account(&token_config_address(&mint)).is_none());
+}
+
+#[test]
+fn create_audio_token_pays_creation_fee_to_treasury() {
+    let mut fixture = Fixture::new();
+    let treasury_before = fixture.svm.lamports(&fixture.treasury);
+
+    fixture.create_token();
+
+    assert_eq!(
+        fixture.svm.lamports(&fixture.treasury),
+        treasury_before + CREATION_FEE
+    );
+}
+
+#[test]
+fn buy_tokens_rejects_foreign_fee_recipient() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let buyer = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+    let attacker = fixture.funded_wallet(LAMPORTS_PER_SOL);
+
+    let result = fixture.svm.send(
+        &[buy_tokens_ix(
+            &buyer,
+            &mint,
+            &attacker,
+            LAMPORTS_PER_SOL,
+            0,
+            None,
+        )],
+        &[&buyer],
+    );
+
+    assert_error(result, ErrorCode::InvalidFeeRecipient);
+    assert_eq!(fixture.svm.lamports(&attacker), LAMPORTS_PER_SOL);
+}
+
+#[test]
+fn sell_tokens_rejects_foreign_fee_recipient() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let trader = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+    let attacker = fixture.funded_wallet(LAMPORTS_PER_SOL);
+    fixture.buy(&trader, &mint, LAMPORTS_PER_SOL / 100).unwrap();
+    let balance =
+        fixture
+            .svm
+            .token_balance(&anchor_spl::associated_token::get_associated_token_address(
+                &trader, &mint,
+            ));
+
+    let result = fixture.svm.send(
+        &[sell_tokens_ix(&trader, &mint, &attacker, balance, 0, None)],
+        &[&trader],
+    );
+
+    assert_error(result, ErrorCode::InvalidFeeRecipient);
+    assert_eq!(fixture.svm.lamports(&attacker), LAMPORTS_PER_SOL);
+}
+
+#[test]
+fn trade_fees_follow_updated_fee_recipient() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let trader = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+    let new_treasury = fixture.funded_wallet(LAMPORTS_PER_SOL);
+    let old_treasury = fixture.treasury;
+
+    let admin = fixture.admin;
+    fixture
+        .svm
+        .send(
+            &[update_platform_config_ix(
+                &admin,
+                UpdatePlatformConfigParams {
+                    fee_recipient: Some(new_treasury),
+                    ..Default::default()
+                },
+            )],
+            &[&admin],
+        )
+        .unwrap();
+
+    let sol_amount = LAMPORTS_PER_SOL / 100;
+    let result = fixture.svm.send(
+        &[buy_tokens_ix(
+            &trader,
+            &mint,
+            &old_treasury,
+            sol_amount,
+            0,
+            None,
+        )],
+        &[&trader],
+    );
+    assert_error(result, ErrorCode::InvalidFeeRecipient);
+
+    fixture
+        .svm
+        .send(
+            &[buy_tokens_ix(
+                &trader,
+                &mint,
+                &new_treasury,
+                sol_amount,
+                0,
+                None,
+            )],
+            &[&trader],
+        )
+        .unwrap();
+    assert_eq!(
+        fixture.svm.lamports(&new_treasury),
+        LAMPORTS_PER_SOL + sol_amount * TRADE_FEE_BPS / 10_000
+    );
+}
@@ -0,0 +1,191 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address;
+use audio_token_platform::{
+    ErrorCode, LpAccount, LpFeesClaimed, LpPosition, TokenBought, TokenSold,
+    UpdatePlatformConfigParams,
+};
+use common::*;
+
+const LP_SHARE_BPS: u64 = 5_000;
+const CREATOR_SHARE_BPS: u64 = 2_000;
+
+fn fixture_with_split() -> Fixture {
+    let mut fixture = Fixture::new();
+    fixture.update_platform_config(UpdatePlatformConfigParams {
+        lp_fee_share_bps: Some(LP_SHARE_BPS),
+        creator_fee_share_bps: Some(CREATOR_SHARE_BPS),
+        ..Default::default()
+    });
+    fixture
+}
+
+/// Buys tokens with a fresh wallet and adds all of them as liquidity.
+fn add_provider(fixture: &mut Fixture, mint: &Pubkey) -> Pubkey {
+    let provider = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+    fixture
+        .buy(&provider, mint, LAMPORTS_PER_SOL / 100)
+        .unwrap();
+    let tokens = fixture
+        .svm
+        .token_balance(&get_associated_token_address(&provider, mint));
+    fixture
+        .svm
+        .send(
+            &[add_liquidity_ix(
+                &provider,
+                mint,
+                LAMPORTS_PER_SOL,
+                tokens,
+                0,
+            )],
+            &[&provider],
+        )
+        .unwrap();
+    provider
+}
+
+fn lp_position(fixture: &Fixture, mint: &Pubkey, provider: &Pubkey) -> LpPosition {
+    fixture
+        .svm
+        .anchor_account(&lp_position_address(mint, provider))
+}
+
+fn claim(
+    fixture: &mut Fixture,
+    provider: &Pubkey,
+    mint: &Pubkey,
+) -> std::result::Result<(), TxError> {
+    fixture
+        .svm
+        .send(&[claim_lp_fees_ix(provider, mint)], &[provider])
+}
+
+#[test]
+fn update_platform_config_rejects_shares_above_the_whole_fee() {
+    let mut fixture = Fixture::new();
+    let admin = fixture.admin;
+    let result = fixture.svm.send(
+        &[update_platform_config_ix(
+            &admin,
+            UpdatePlatformConfigParams {
+                lp_fee_share_bps: Some(6_000),
+                creator_fee_share_bps: Some(4_001),
+                ..Default::default()
+            },
+        )],
+        &[&admin],
+    );
+
+    assert_error(result, ErrorCode::InvalidFeeConfig);
+}
+
+#[test]
+fn trade_fee_is_split_between_platform_creator_and_lps() {
+    let mut fixture = fixture_with_split();
+    let (_, mint) = fixture.create_token();
+    add_provider(&mut fixture, &mint);
+
+    let trader = fixture.funded_wallet(LAMPORTS_PER_SOL);
+    let treasury_before = fixture.svm.lamports(&fixture.treasury);
+    let vault_before = fixture.svm.lamports(&creator_fee_vault_address(&mint));
+    let lp_account_before = fixture.svm.lamports(&lp_account_address(&mint));
+    fixture.buy(&trader, &mint, LAMPORTS_PER_SOL / 50).unwrap();
+
+    let event = fixture.svm.events::<TokenBought>().pop().unwrap();
+    assert_eq!(event.creator_fee, event.fee * CREATOR_SHARE_BPS / 10_000);
+    assert_eq!(event.lp_fee, event.fee * LP_SHARE_BPS / 10_000);
+    assert_eq!(
+        fixture.svm.lamports(&fixture.treasury) - treasury_before,
+        event.fee - event.creator_fee - event.lp_fee
+    );
+    assert_eq!(
+        fixture.svm.lamports(&creator_fee_vault_address(&mint)) - vault_before,
+        event.creator_fee
+    );
+    assert_eq!(
+        fixture.svm.lamports(&lp_account_address(&mint)) - lp_account_before,
+        event.lp_fee
+    );
+}
+
+#[test]
+fn lp_share_goes_to_the_platform_without_providers() {
+    let mut fixture = fixture_with_split();
+    let (_, mint) = fixture.create_token();
+
+    let trader = fixture.funded_wallet(LAMPORTS_PER_SOL);
+    let treasury_before = fixture.svm.lamports(&fixture.treasury);
+    fixture.buy(&trader, &mint, LAMPORTS_PER_SOL / 50).unwrap();
+
+    let event = fixture.svm.events::<TokenBought>().pop().unwrap();
+    assert_eq!(event.lp_fee, 0);
+    assert_eq!(
+        fixture.svm.lamports(&fixture.treasury) - treasury_before,
+        event.fee - event.creator_fee
+    );
+}
+
+#[test]
+fn providers_claim_fees_in_proportion_to_their_shares() {
+    let mut fixture = fixture_with_split();
+    let (_, mint) = fixture.create_token();
+    let first = add_provider(&mut fixture, &mint);
+    let second = add_provider(&mut fixture, &mint);
+    // The second provider's buy paid LP fees to the first one alone
+    claim(&mut fixture, &first, &mint).unwrap();
+
+    let trader = fixture.funded_wallet(LAMPORTS_PER_SOL);
+    fixture.buy(&trader, &mint, LAMPORTS_PER_SOL / 50).unwrap();
+    let mut accrued = fixture.svm.events::<TokenBought>().pop().unwrap().lp_fee;
+    let balance = fixture
+        .svm
+        .token_balance(&get_associated_token_address(&trader, &mint));
+    fixture.sell(&trader, &mint, balance).unwrap();
+    accrued += fixture.svm.events::<TokenSold>().pop().unwrap().lp_fee;
+
+    let lp_account: LpAccount = fixture.svm.anchor_account(&lp_account_address(&mint));
+    let total_shares = lp_account.provider_liquidity() as u128;
+    for provider in [first, second] {
+        let shares = lp_position(&fixture, &mint, &provider).shares as u128;
+        let before = fixture.svm.lamports(&provider);
+        claim(&mut fixture, &provider, &mint).unwrap();
+
+        let event = fixture.svm.events::<LpFeesClaimed>().pop().unwrap();
+        assert_eq!(event.provider, provider);
+        assert_eq!(fixture.svm.lamports(&provider) - before, event.amount);
+        let expected = (accrued as u128 * shares / total_shares) as u64;
+        assert!(event.amount <= expected + 1 && event.amount + 2 >= expected);
+    }
+
+    assert_error(
+        claim(&mut fixture, &first, &mint),
+        ErrorCode::NothingToClaim,
+    );
+}
+
+#[test]
+fn fees_accrued_before_removal_stay_claimable() {
+    let mut fixture = fixture_with_split();
+    let (_, mint) = fixture.create_token();
+    let provider = add_provider(&mut fixture, &mint);
+
+    let trader = fixture.funded_wallet(LAMPORTS_PER_SOL);
+    fixture.buy(&trader, &mint, LAMPORTS_PER_SOL / 50).unwrap();
+    let lp_fee = fixture.svm.events::<TokenBought>().pop().unwrap().lp_fee;
+
+    let shares = lp_position(&fixture, &mint, &provider).shares;
+    fixture
+        .svm
+        .send(
+            &[remove_liquidity_ix(&provider, &mint, shares, 0, 0)],
+            &[&provider],
+        )
+        .unwrap();
+
+    claim(&mut fixture, &provider, &mint).unwrap();
+    let event = fixture.svm.events::<LpFeesClaimed>().pop().unwrap();
+    assert!(event.amount <= lp_fee && event.amount + 1 >= lp_fee);
+    assert_eq!(lp_position(&fixture, &mint, &provider).shares, 0);
+}
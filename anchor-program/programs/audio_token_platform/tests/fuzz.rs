@@ -0,0 +1,435 @@
+//! Instruction-sequence fuzzer: runs random sequences of create, trade and
+//! liquidity instructions against the in-process SVM and checks the
+//! program's accounting invariants after every step, whether the step
+//! succeeded or not. Each sequence ends by withdrawing every LP position and
+//! selling every holder's balance back into curves still trading, which
+//! must all succeed.
+//!
+//! `PROPTEST_CASES` raises the number of sequences for longer runs.
+
+mod common;
+
+use anchor_lang::prelude::{Pubkey, Rent};
+use anchor_spl::associated_token::get_associated_token_address;
+use audio_token_platform::{
+    CreatorFeeVault, CurveParams, CurveType, LpAccount, LpPosition, UpdatePlatformConfigParams,
+    FEE_PER_SHARE_SCALE,
+};
+use common::*;
+use proptest::prelude::*;
+
+const TRADERS: usize = 3;
+const MAX_TOKENS: usize = 3;
+const MAX_CREATOR_FEE_BPS: u64 = 100;
+
+/// Starting reserves that pass validation for each curve type.
+fn curve_params(curve_type: CurveType) -> CurveParams {
+    match curve_type {
+        CurveType::ConstantProduct => CURVE_PARAMS,
+        CurveType::Linear | CurveType::Exponential => CurveParams {
+            curve_type,
+            virtual_sol_reserves: LAMPORTS_PER_SOL / 10,
+            virtual_token_reserves: CURVE_PARAMS.virtual_token_reserves,
+        },
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Op {
+    Create {
+        curve_type: CurveType,
+        creator_fee_bps: u64,
+    },
+    Buy {
+        token: usize,
+        trader: usize,
+        sol_amount: u64,
+    },
+    BuyExact {
+        token: usize,
+        trader: usize,
+        token_amount: u64,
+    },
+    Sell {
+        token: usize,
+        trader: usize,
+        balance_bps: u64,
+    },
+    SellExact {
+        token: usize,
+        trader: usize,
+        sol_amount: u64,
+    },
+    AddLiquidity {
+        token: usize,
+        trader: usize,
+        sol_amount: u64,
+        balance_bps: u64,
+    },
+    RemoveLiquidity {
+        token: usize,
+        trader: usize,
+        shares_bps: u64,
+    },
+    ClaimLpFees {
+        token: usize,
+        trader: usize,
+    },
+    ClaimCreatorFees {
+        token: usize,
+    },
+    RoundTrip {
+        token: usize,
+        trader: usize,
+        sol_amount: u64,
+    },
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    let token = 0..MAX_TOKENS;
+    let trader = 0..TRADERS;
+    // Trades are small next to the graduation threshold, so most sequences
+    // trade for a while before the curve completes
+    let sol_amount = 1..GRADUATION_THRESHOLD / 5;
+    let bps = 1..=10_000u64;
+    let curve_type = prop_oneof![
+        Just(CurveType::ConstantProduct),
+        Just(CurveType::Linear),
+        Just(CurveType::Exponential),
+    ];
+    prop_oneof![
+        1 => (curve_type, 0..=MAX_CREATOR_FEE_BPS).prop_map(|(curve_type, creator_fee_bps)| {
+            Op::Create { curve_type, creator_fee_bps }
+        }),
+        4 => (token.clone(), trader.clone(), sol_amount.clone())
+            .prop_map(|(token, trader, sol_amount)| Op::Buy { token, trader, sol_amount }),
+        2 => (token.clone(), trader.clone(), 1..10_000_000 * 1_000_000_000u64)
+            .prop_map(|(token, trader, token_amount)| Op::BuyExact { token, trader, token_amount }),
+        4 => (token.clone(), trader.clone(), bps.clone())
+            .prop_map(|(token, trader, balance_bps)| Op::Sell { token, trader, balance_bps }),
+        2 => (token.clone(), trader.clone(), sol_amount.clone())
+            .prop_map(|(token, trader, sol_amount)| Op::SellExact { token, trader, sol_amount }),
+        2 => (token.clone(), trader.clone(), sol_amount.clone(), bps.clone()).prop_map(
+            |(token, trader, sol_amount, balance_bps)| Op::AddLiquidity {
+                token,
+                trader,
+                sol_amount,
+                balance_bps,
+            }
+        ),
+        2 => (token.clone(), trader.clone(), bps)
+            .prop_map(|(token, trader, shares_bps)| Op::RemoveLiquidity { token, trader, shares_bps }),
+        1 => (token.clone(), trader.clone())
+            .prop_map(|(token, trader)| Op::ClaimLpFees { token, trader }),
+        1 => token.clone().prop_map(|token| Op::ClaimCreatorFees { token }),
+        2 => (token, trader, sol_amount)
+            .prop_map(|(token, trader, sol_amount)| Op::RoundTrip { token, trader, sol_amount }),
+    ]
+}
+
+fn bps_of(amount: u64, bps: u64) -> u64 {
+    (amount as u128 * bps as u128 / 10_000) as u64
+}
+
+struct Harness {
+    fixture: Fixture,
+    traders: Vec<Pubkey>,
+    /// `(creator, mint)` of every token created so far
+    tokens: Vec<(Pubkey, Pubkey)>,
+}
+
+impl Harness {
+    fn new() -> Self {
+        let mut fixture = Fixture::new();
+        fixture.update_platform_config(UpdatePlatformConfigParams {
+            lp_fee_share_bps: Some(5_000),
+            creator_fee_share_bps: Some(2_000),
+            max_creator_fee_bps: Some(MAX_CREATOR_FEE_BPS),
+            ..Default::default()
+        });
+        let traders = (0..TRADERS)
+            .map(|_| fixture.funded_wallet(100 * LAMPORTS_PER_SOL))
+            .collect();
+        let token = fixture.create_token_with_royalty(MAX_CREATOR_FEE_BPS / 2);
+        Self {
+            fixture,
+            traders,
+            tokens: vec![token],
+        }
+    }
+
+    fn mint(&self, token: usize) -> Pubkey {
+        self.tokens[token % self.tokens.len()].1
+    }
+
+    fn token_balance(&self, owner: &Pubkey, mint: &Pubkey) -> u64 {
+        let ata = get_associated_token_address(owner, mint);
+        self.fixture
+            .svm
+            .account(&ata)
+            .map_or(0, |_| self.fixture.svm.token_balance(&ata))
+    }
+
+    fn position(&self, mint: &Pubkey, provider: &Pubkey) -> Option<LpPosition> {
+        let address = lp_position_address(mint, provider);
+        self.fixture
+            .svm
+            .account(&address)
+            .map(|_| self.fixture.svm.anchor_account(&address))
+    }
+
+    fn send(
+        &mut self,
+        ix: anchor_lang::solana_program::instruction::Instruction,
+        signer: &Pubkey,
+    ) -> std::result::Result<(), TxError> {
+        self.fixture.svm.send(&[ix], &[signer])
+    }
+
+    fn apply(&mut self, op: &Op) {
+        match *op {
+            Op::Create {
+                curve_type,
+                creator_fee_bps,
+            } => {
+                if self.tokens.len() < MAX_TOKENS {
+                    let token = self
+                        .fixture
+                        .create_token_from(curve_params(curve_type), creator_fee_bps);
+                    self.tokens.push(token);
+                }
+            }
+            Op::Buy {
+                token,
+                trader,
+                sol_amount,
+            } => {
+                let (mint, trader) = (self.mint(token), self.traders[trader]);
+                let _ = self.fixture.buy(&trader, &mint, sol_amount);
+            }
+            Op::BuyExact {
+                token,
+                trader,
+                token_amount,
+            } => {
+                let (mint, trader) = (self.mint(token), self.traders[trader]);
+                let treasury = self.fixture.treasury;
+                let ix = buy_exact_tokens_out_ix(
+                    &trader,
+                    &mint,
+                    &treasury,
+                    token_amount,
+                    u64::MAX,
+                    None,
+                );
+                let _ = self.send(ix, &trader);
+            }
+            Op::Sell {
+                token,
+                trader,
+                balance_bps,
+            } => {
+                let (mint, trader) = (self.mint(token), self.traders[trader]);
+                let amount = bps_of(self.token_balance(&trader, &mint), balance_bps);
+                let _ = self.fixture.sell(&trader, &mint, amount);
+            }
+            Op::SellExact {
+                token,
+                trader,
+                sol_amount,
+            } => {
+                let (mint, trader) = (self.mint(token), self.traders[trader]);
+                let treasury = self.fixture.treasury;
+                let ix =
+                    sell_for_exact_sol_ix(&trader, &mint, &treasury, sol_amount, u64::MAX, None);
+                let _ = self.send(ix, &trader);
+            }
+            Op::AddLiquidity {
+                token,
+                trader,
+                sol_amount,
+                balance_bps,
+            } => {
+                let (mint, trader) = (self.mint(token), self.traders[trader]);
+                let tokens = bps_of(self.token_balance(&trader, &mint), balance_bps);
+                let _ = self.send(
+                    add_liquidity_ix(&trader, &mint, sol_amount, tokens, 0),
+                    &trader,
+                );
+            }
+            Op::RemoveLiquidity {
+                token,
+                trader,
+                shares_bps,
+            } => {
+                let (mint, trader) = (self.mint(token), self.traders[trader]);
+                let shares = self.position(&mint, &trader).map_or(0, |p| p.shares);
+                let ix = remove_liquidity_ix(&trader, &mint, bps_of(shares, shares_bps), 0, 0);
+                let _ = self.send(ix, &trader);
+            }
+            Op::ClaimLpFees { token, trader } => {
+                let (mint, trader) = (self.mint(token), self.traders[trader]);
+                let _ = self.send(claim_lp_fees_ix(&trader, &mint), &trader);
+            }
+            Op::ClaimCreatorFees { token } => {
+                let (creator, mint) = self.tokens[token % self.tokens.len()];
+                let _ = self.send(claim_creator_fees_ix(&creator, &mint), &creator);
+            }
+            Op::RoundTrip {
+                token,
+                trader,
+                sol_amount,
+            } => {
+                let (mint, trader) = (self.mint(token), self.traders[trader]);
+                let lamports_before = self.fixture.svm.lamports(&trader);
+                let tokens_before = self.token_balance(&trader, &mint);
+                if self.fixture.buy(&trader, &mint, sol_amount).is_err() {
+                    return;
+                }
+                let bought = self.token_balance(&trader, &mint) - tokens_before;
+                if self.fixture.sell(&trader, &mint, bought).is_ok() {
+                    assert!(
+                        self.fixture.svm.lamports(&trader) <= lamports_before,
+                        "buying then selling {sol_amount} lamports made a profit"
+                    );
+                }
+            }
+        }
+    }
+
+    /// Withdraws every LP position in full, then sells every trader's whole
+    /// balance, on each token whose curve is still trading. A failure means
+    /// some holder was left unable to exit.
+    fn exit_all(&mut self) {
+        for (_, mint) in self.tokens.clone() {
+            let lp_account: LpAccount = self.fixture.svm.anchor_account(&lp_account_address(&mint));
+            if self
+                .fixture
+                .token_config(&mint)
+                .is_curve_complete(&lp_account)
+                .unwrap()
+            {
+                continue;
+            }
+            for trader in self.traders.clone() {
+                let shares = self.position(&mint, &trader).map_or(0, |p| p.shares);
+                if shares > 0 {
+                    let ix = remove_liquidity_ix(&trader, &mint, shares, 0, 0);
+                    self.send(ix, &trader).unwrap_or_else(|error| {
+                        panic!("removing {shares} shares failed: {error:?}")
+                    });
+                    self.check_invariants();
+                }
+            }
+            for trader in self.traders.clone() {
+                let balance = self.token_balance(&trader, &mint);
+                if balance > 0 {
+                    self.fixture
+                        .sell(&trader, &mint, balance)
+                        .unwrap_or_else(|error| {
+                            panic!("selling {balance} tokens failed: {error:?}")
+                        });
+                    self.check_invariants();
+                }
+            }
+        }
+    }
+
+    fn rent_exempt(&self, address: &Pubkey) -> u64 {
+        let data_len = self.fixture.svm.account(address).unwrap().data.len();
+        Rent::default().minimum_balance(data_len)
+    }
+
+    fn check_invariants(&self) {
+        let svm = &self.fixture.svm;
+        for (creator, mint) in &self.tokens {
+            let token_config = self.fixture.token_config(mint);
+
+            // The curve PDA holds every lamport of its real SOL reserves
+            let curve_address = token_config_address(mint);
+            assert!(
+                svm.lamports(&curve_address)
+                    >= token_config.real_sol_reserves + self.rent_exempt(&curve_address),
+                "curve lamports below real SOL reserves plus rent"
+            );
+            assert!(token_config.real_sol_reserves <= token_config.virtual_sol_reserves);
+
+            // The reserve vault holds exactly the real token reserves
+            if !token_config.graduated {
+                assert_eq!(
+                    svm.token_balance(&reserve_address(mint)),
+                    token_config.real_token_reserves,
+                    "reserve vault balance differs from real token reserves"
+                );
+            }
+
+            // No tokens appear or disappear
+            let held: u64 = self
+                .traders
+                .iter()
+                .chain([creator])
+                .map(|owner| self.token_balance(owner, mint))
+                .sum();
+            assert_eq!(
+                held + svm.token_balance(&reserve_address(mint))
+                    + svm.token_balance(&creator_vault_address(mint))
+                    + svm.token_balance(&locked_vault_address(mint)),
+                token_config.total_supply,
+                "token supply not conserved"
+            );
+
+            // LP shares and fees are fully backed
+            let lp_account: LpAccount = svm.anchor_account(&lp_account_address(mint));
+            let positions: Vec<LpPosition> = self
+                .traders
+                .iter()
+                .filter_map(|trader| self.position(mint, trader))
+                .collect();
+            assert_eq!(
+                lp_account.liquidity,
+                lp_account.locked_liquidity + positions.iter().map(|p| p.shares).sum::<u64>(),
+                "LP shares don't add up"
+            );
+            let claimable: u128 = positions
+                .iter()
+                .map(|p| {
+                    let accrued = p.shares as u128 * lp_account.fee_per_share / FEE_PER_SHARE_SCALE;
+                    p.unclaimed_fees as u128 + accrued.saturating_sub(p.fee_debt)
+                })
+                .sum();
+            let lp_address = lp_account_address(mint);
+            assert!(
+                svm.lamports(&lp_address) as u128
+                    >= self.rent_exempt(&lp_address) as u128 + claimable,
+                "LP fees not backed by the LP account"
+            );
+
+            // The creator fee vault holds exactly what is owed
+            let vault_address = creator_fee_vault_address(mint);
+            let vault: CreatorFeeVault = svm.anchor_account(&vault_address);
+            assert_eq!(
+                svm.lamports(&vault_address) - self.rent_exempt(&vault_address),
+                vault.accrued_fees - vault.claimed_fees,
+                "creator fee vault out of sync"
+            );
+        }
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig {
+        cases: 32,
+        ..ProptestConfig::default()
+    })]
+
+    #[test]
+    fn random_instruction_sequences_keep_invariants(ops in prop::collection::vec(op(), 1..40)) {
+        let mut harness = Harness::new();
+        harness.check_invariants();
+        for op in &ops {
+            harness.apply(op);
+            harness.check_invariants();
+        }
+        harness.exit_all();
+    }
+}
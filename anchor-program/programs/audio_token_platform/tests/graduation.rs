@@ -0,0 +1,269 @@
+mod common;
+
+use anchor_lang::prelude::{Pubkey, Rent};
+use anchor_spl::associated_token::get_associated_token_address;
+use audio_token_platform::{
+    ErrorCode, LpAccount, LpPosition, MigrationVault, TokenConfig, UpdatePlatformConfigParams,
+};
+use common::*;
+
+// Only the post-fee amount counts towards real reserves
+const GRADUATING_BUY: u64 = GRADUATION_THRESHOLD * 101 / 100;
+const LOCKED_ALLOCATION: u64 = TOTAL_SUPPLY - TOTAL_SUPPLY / 10 - TOTAL_SUPPLY / 20;
+
+fn lp_account(fixture: &Fixture, mint: &Pubkey) -> LpAccount {
+    fixture.svm.anchor_account(&lp_account_address(mint))
+}
+
+/// A provider who bought half the threshold's worth of tokens and deposited
+/// all of them, with over twice the threshold in SOL, as liquidity.
+fn deep_liquidity_provider(fixture: &mut Fixture, mint: &Pubkey) -> Pubkey {
+    let provider = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+    fixture
+        .buy(&provider, mint, GRADUATION_THRESHOLD / 2)
+        .unwrap();
+    let tokens = fixture
+        .svm
+        .token_balance(&get_associated_token_address(&provider, mint));
+    fixture
+        .svm
+        .send(
+            &[add_liquidity_ix(
+                &provider,
+                mint,
+                LAMPORTS_PER_SOL,
+                tokens,
+                0,
+            )],
+            &[&provider],
+        )
+        .unwrap();
+    assert!(fixture.token_config(mint).real_sol_reserves > 2 * GRADUATION_THRESHOLD);
+    provider
+}
+
+#[test]
+fn token_inherits_platform_graduation_threshold() {
+    let mut fixture = Fixture::new();
+    fixture.update_platform_config(UpdatePlatformConfigParams {
+        graduation_threshold: Some(GRADUATION_THRESHOLD / 2),
+        ..Default::default()
+    });
+    let (_, mint) = fixture.create_token();
+
+    let token_config = fixture.token_config(&mint);
+    assert_eq!(token_config.graduation_threshold, GRADUATION_THRESHOLD / 2);
+    assert!(!token_config.graduated);
+}
+
+#[test]
+fn graduate_fails_below_threshold() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let payer = fixture.funded_wallet(LAMPORTS_PER_SOL);
+
+    let result = fixture.svm.send(&[graduate_ix(&payer, &mint)], &[&payer]);
+
+    assert_error(result, ErrorCode::GraduationThresholdNotReached);
+}
+
+#[test]
+fn curve_trading_freezes_once_threshold_is_reached() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let trader = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+
+    fixture.buy(&trader, &mint, GRADUATING_BUY).unwrap();
+    assert!(fixture
+        .token_config(&mint)
+        .is_curve_complete(&lp_account(&fixture, &mint))
+        .unwrap());
+
+    assert_error(
+        fixture.buy(&trader, &mint, LAMPORTS_PER_SOL / 100),
+        ErrorCode::CurveComplete,
+    );
+    let balance = fixture
+        .svm
+        .token_balance(&get_associated_token_address(&trader, &mint));
+    assert_error(
+        fixture.sell(&trader, &mint, balance),
+        ErrorCode::CurveComplete,
+    );
+}
+
+#[test]
+fn graduate_moves_reserves_to_migration_vault() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let trader = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+    fixture.buy(&trader, &mint, GRADUATING_BUY).unwrap();
+
+    let token_config_key = token_config_address(&mint);
+    let migrated_sol = fixture.token_config(&mint).real_sol_reserves;
+    let curve_tokens = fixture.svm.token_balance(&reserve_address(&mint));
+
+    let payer = fixture.funded_wallet(LAMPORTS_PER_SOL);
+    fixture
+        .svm
+        .send(&[graduate_ix(&payer, &mint)], &[&payer])
+        .unwrap();
+
+    let rent = Rent::default();
+    let token_config_rent =
+        rent.minimum_balance(8 + <TokenConfig as anchor_lang::Space>::INIT_SPACE);
+
+    let vault_key = migration_vault_address(&mint);
+    let vault: MigrationVault = fixture.svm.anchor_account(&vault_key);
+    assert_eq!(vault.mint, mint);
+    assert_eq!(vault.sol_amount, migrated_sol);
+    assert_eq!(vault.token_amount, curve_tokens + LOCKED_ALLOCATION);
+    assert_eq!(
+        fixture.svm.lamports(&vault_key),
+        rent.minimum_balance(8 + <MigrationVault as anchor_lang::Space>::INIT_SPACE) + migrated_sol
+    );
+    assert_eq!(
+        fixture
+            .svm
+            .token_balance(&get_associated_token_address(&vault_key, &mint)),
+        curve_tokens + LOCKED_ALLOCATION
+    );
+    assert_eq!(fixture.svm.token_balance(&locked_vault_address(&mint)), 0);
+    assert_eq!(fixture.svm.lamports(&token_config_key), token_config_rent);
+    assert_eq!(fixture.svm.token_balance(&reserve_address(&mint)), 0);
+
+    let token_config = fixture.token_config(&mint);
+    assert!(token_config.graduated);
+    assert_eq!(token_config.virtual_sol_reserves, 0);
+    assert_eq!(token_config.real_sol_reserves, 0);
+    assert_eq!(token_config.virtual_token_reserves, 0);
+    assert_eq!(token_config.real_token_reserves, 0);
+}
+
+#[test]
+fn graduate_cannot_run_twice() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let trader = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+    fixture.buy(&trader, &mint, GRADUATING_BUY).unwrap();
+    let payer = fixture.funded_wallet(LAMPORTS_PER_SOL);
+    fixture
+        .svm
+        .send(&[graduate_ix(&payer, &mint)], &[&payer])
+        .unwrap();
+    let vault_key = migration_vault_address(&mint);
+    let vault_lamports = fixture.svm.lamports(&vault_key);
+
+    // The migration vault already exists, so its `init` fails before the
+    // handler's graduated check is reached.
+    let other_payer = fixture.funded_wallet(LAMPORTS_PER_SOL);
+    let result = fixture
+        .svm
+        .send(&[graduate_ix(&other_payer, &mint)], &[&other_payer]);
+
+    assert!(result.is_err());
+    assert_eq!(fixture.svm.lamports(&vault_key), vault_lamports);
+}
+
+#[test]
+fn liquidity_deposits_do_not_count_towards_graduation() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    deep_liquidity_provider(&mut fixture, &mint);
+
+    let token_config = fixture.token_config(&mint);
+    let lp_account = lp_account(&fixture, &mint);
+    assert!(token_config.curve_sol_reserves(&lp_account).unwrap() < GRADUATION_THRESHOLD);
+    assert!(!token_config.is_curve_complete(&lp_account).unwrap());
+
+    let payer = fixture.funded_wallet(LAMPORTS_PER_SOL);
+    assert_error(
+        fixture.svm.send(&[graduate_ix(&payer, &mint)], &[&payer]),
+        ErrorCode::GraduationThresholdNotReached,
+    );
+    let trader = fixture.funded_wallet(LAMPORTS_PER_SOL);
+    fixture.buy(&trader, &mint, LAMPORTS_PER_SOL / 100).unwrap();
+}
+
+#[test]
+fn liquidity_providers_redeem_after_graduation() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let provider = deep_liquidity_provider(&mut fixture, &mint);
+
+    // LPs take their share of each buy, so it takes several to graduate
+    let trader = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+    while !fixture
+        .token_config(&mint)
+        .is_curve_complete(&lp_account(&fixture, &mint))
+        .unwrap()
+    {
+        fixture.buy(&trader, &mint, GRADUATION_THRESHOLD).unwrap();
+    }
+
+    let token_config = fixture.token_config(&mint);
+    let (lp_sol, lp_tokens) = token_config
+        .lp_reserves(&lp_account(&fixture, &mint))
+        .unwrap();
+    assert!(lp_sol > 0 && lp_tokens > 0);
+
+    let payer = fixture.funded_wallet(LAMPORTS_PER_SOL);
+    fixture
+        .svm
+        .send(&[graduate_ix(&payer, &mint)], &[&payer])
+        .unwrap();
+
+    let vault: MigrationVault = fixture.svm.anchor_account(&migration_vault_address(&mint));
+    assert_eq!(vault.sol_amount, token_config.real_sol_reserves - lp_sol);
+    assert_eq!(
+        vault.token_amount,
+        token_config.real_token_reserves - lp_tokens + LOCKED_ALLOCATION
+    );
+    let graduated = fixture.token_config(&mint);
+    assert_eq!(graduated.real_sol_reserves, lp_sol);
+    assert_eq!(graduated.real_token_reserves, lp_tokens);
+    assert_eq!(
+        fixture.svm.token_balance(&reserve_address(&mint)),
+        lp_tokens
+    );
+
+    // No new deposits once graduated
+    assert_error(
+        fixture.svm.send(
+            &[add_liquidity_ix(
+                &trader,
+                &mint,
+                LAMPORTS_PER_SOL / 100,
+                u64::MAX,
+                0,
+            )],
+            &[&trader],
+        ),
+        ErrorCode::CurveComplete,
+    );
+
+    // The only provider redeems every share for everything LPs were owed
+    let provider_ata = get_associated_token_address(&provider, &mint);
+    let lamports_before = fixture.svm.lamports(&provider);
+    let tokens_before = fixture.svm.token_balance(&provider_ata);
+    let position: LpPosition = fixture
+        .svm
+        .anchor_account(&lp_position_address(&mint, &provider));
+    fixture
+        .svm
+        .send(
+            &[remove_liquidity_ix(&provider, &mint, position.shares, 0, 0)],
+            &[&provider],
+        )
+        .unwrap();
+
+    assert_eq!(fixture.svm.lamports(&provider) - lamports_before, lp_sol);
+    assert_eq!(
+        fixture.svm.token_balance(&provider_ata) - tokens_before,
+        lp_tokens
+    );
+    let token_config = fixture.token_config(&mint);
+    assert_eq!(token_config.real_sol_reserves, 0);
+    assert_eq!(token_config.real_token_reserves, 0);
+    assert_eq!(fixture.svm.token_balance(&reserve_address(&mint)), 0);
+}
@@ -0,0 +1,321 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address;
+use audio_token_platform::{CurveParams, CurveType, ErrorCode, LpAccount, LpPosition};
+use common::*;
+
+const LINEAR: CurveParams = CurveParams {
+    curve_type: CurveType::Linear,
+    virtual_sol_reserves: LAMPORTS_PER_SOL / 10,
+    virtual_token_reserves: CURVE_PARAMS.virtual_token_reserves,
+};
+const EXPONENTIAL: CurveParams = CurveParams {
+    curve_type: CurveType::Exponential,
+    ..LINEAR
+};
+
+/// A funded wallet holding tokens bought from the curve.
+fn provider_with_tokens(fixture: &mut Fixture, mint: &Pubkey) -> (Pubkey, u64) {
+    let provider = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+    fixture
+        .buy(&provider, mint, LAMPORTS_PER_SOL / 100)
+        .unwrap();
+    let tokens = fixture
+        .svm
+        .token_balance(&get_associated_token_address(&provider, mint));
+    (provider, tokens)
+}
+
+fn lp_position(fixture: &Fixture, mint: &Pubkey, provider: &Pubkey) -> LpPosition {
+    fixture
+        .svm
+        .anchor_account(&lp_position_address(mint, provider))
+}
+
+fn liquidity(fixture: &Fixture, mint: &Pubkey) -> u64 {
+    fixture
+        .svm
+        .anchor_account::<LpAccount>(&lp_account_address(mint))
+        .liquidity
+}
+
+fn add(
+    fixture: &mut Fixture,
+    provider: &Pubkey,
+    mint: &Pubkey,
+    sol_amount: u64,
+    token_amount: u64,
+) -> std::result::Result<(), TxError> {
+    let ix = add_liquidity_ix(provider, mint, sol_amount, token_amount, 0);
+    fixture.svm.send(&[ix], &[provider])
+}
+
+fn remove(
+    fixture: &mut Fixture,
+    provider: &Pubkey,
+    mint: &Pubkey,
+    lp_share: u64,
+) -> std::result::Result<(), TxError> {
+    let ix = remove_liquidity_ix(provider, mint, lp_share, 0, 0);
+    fixture.svm.send(&[ix], &[provider])
+}
+
+#[test]
+fn add_liquidity_mints_proportional_shares_to_the_position() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let (provider, tokens) = provider_with_tokens(&mut fixture, &mint);
+
+    let liquidity_before = liquidity(&fixture, &mint);
+    let token_config = fixture.token_config(&mint);
+    let sol_amount = LAMPORTS_PER_SOL / 1000;
+    add(&mut fixture, &provider, &mint, sol_amount, tokens).unwrap();
+
+    let expected = (liquidity_before as u128 * sol_amount as u128
+        / token_config.virtual_sol_reserves as u128) as u64;
+    let position = lp_position(&fixture, &mint, &provider);
+    assert_eq!(position.mint, mint);
+    assert_eq!(position.provider, provider);
+    assert_eq!(position.shares, expected);
+    assert_eq!(liquidity(&fixture, &mint), liquidity_before + expected);
+}
+
+#[test]
+fn add_liquidity_takes_only_the_amounts_matching_the_reserve_ratio() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let (provider, tokens) = provider_with_tokens(&mut fixture, &mint);
+    let provider_ata = get_associated_token_address(&provider, &mint);
+
+    // SOL-limited: the token side is matched to the SOL, rounded up
+    let token_config = fixture.token_config(&mint);
+    let sol_amount = LAMPORTS_PER_SOL / 1000;
+    add(&mut fixture, &provider, &mint, sol_amount, tokens).unwrap();
+    let tokens_used = (sol_amount as u128 * token_config.virtual_token_reserves as u128)
+        .div_ceil(token_config.virtual_sol_reserves as u128) as u64;
+    assert_eq!(
+        fixture.svm.token_balance(&provider_ata),
+        tokens - tokens_used
+    );
+
+    // Token-limited: the SOL side is matched to the tokens, rounded up
+    let token_config = fixture.token_config(&mint);
+    let sol_before = fixture.svm.lamports(&provider);
+    let remaining = fixture.svm.token_balance(&provider_ata);
+    add(
+        &mut fixture,
+        &provider,
+        &mint,
+        LAMPORTS_PER_SOL,
+        remaining / 2,
+    )
+    .unwrap();
+    let sol_used = ((remaining / 2) as u128 * token_config.virtual_sol_reserves as u128)
+        .div_ceil(token_config.virtual_token_reserves as u128) as u64;
+    assert_eq!(sol_before - fixture.svm.lamports(&provider), sol_used);
+    assert_eq!(
+        fixture.svm.token_balance(&provider_ata),
+        remaining - remaining / 2
+    );
+
+    // Neither deposit moves the price beyond a lamport of rounding
+    let price = |vs: u64, vt: u64| vs as f64 / vt as f64;
+    let after = fixture.token_config(&mint);
+    let before = price(
+        token_config.virtual_sol_reserves,
+        token_config.virtual_token_reserves,
+    );
+    let moved = price(after.virtual_sol_reserves, after.virtual_token_reserves) / before - 1.0;
+    let lamport = 1.0 / token_config.virtual_sol_reserves as f64;
+    assert!(moved.abs() <= lamport, "price moved by {moved}");
+}
+
+#[test]
+fn add_liquidity_enforces_min_shares_out() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let (provider, tokens) = provider_with_tokens(&mut fixture, &mint);
+
+    let ix = add_liquidity_ix(&provider, &mint, LAMPORTS_PER_SOL / 1000, tokens, u64::MAX);
+    let result = fixture.svm.send(&[ix], &[&provider]);
+
+    assert_error(result, ErrorCode::SlippageExceeded);
+}
+
+#[test]
+fn add_liquidity_rejects_deposits_too_small_for_a_share() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let (provider, _) = provider_with_tokens(&mut fixture, &mint);
+
+    // One token unit is worth far less than a share
+    let result = add(&mut fixture, &provider, &mint, LAMPORTS_PER_SOL, 1);
+
+    assert_error(result, ErrorCode::InvalidPriceRatio);
+}
+
+#[test]
+fn remove_liquidity_burns_shares_from_the_position() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let (provider, tokens) = provider_with_tokens(&mut fixture, &mint);
+    add(
+        &mut fixture,
+        &provider,
+        &mint,
+        LAMPORTS_PER_SOL / 1000,
+        tokens,
+    )
+    .unwrap();
+
+    let shares = lp_position(&fixture, &mint, &provider).shares;
+    let liquidity_before = liquidity(&fixture, &mint);
+    remove(&mut fixture, &provider, &mint, shares / 2).unwrap();
+
+    assert_eq!(
+        lp_position(&fixture, &mint, &provider).shares,
+        shares - shares / 2
+    );
+    assert_eq!(liquidity(&fixture, &mint), liquidity_before - shares / 2);
+}
+
+#[test]
+fn remove_liquidity_enforces_min_outputs() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let (provider, tokens) = provider_with_tokens(&mut fixture, &mint);
+    add(
+        &mut fixture,
+        &provider,
+        &mint,
+        LAMPORTS_PER_SOL / 1000,
+        tokens,
+    )
+    .unwrap();
+    let shares = lp_position(&fixture, &mint, &provider).shares;
+
+    for (min_sol_out, min_tokens_out) in [(u64::MAX, 0), (0, u64::MAX)] {
+        let ix = remove_liquidity_ix(&provider, &mint, shares, min_sol_out, min_tokens_out);
+        assert_error(
+            fixture.svm.send(&[ix], &[&provider]),
+            ErrorCode::SlippageExceeded,
+        );
+    }
+    assert_eq!(lp_position(&fixture, &mint, &provider).shares, shares);
+}
+
+#[test]
+fn providers_cannot_withdraw_more_than_their_own_shares() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let (provider, tokens) = provider_with_tokens(&mut fixture, &mint);
+    add(
+        &mut fixture,
+        &provider,
+        &mint,
+        LAMPORTS_PER_SOL / 1000,
+        tokens,
+    )
+    .unwrap();
+    let (other, other_tokens) = provider_with_tokens(&mut fixture, &mint);
+    add(&mut fixture, &other, &mint, 1_000, other_tokens).unwrap();
+
+    let other_shares = lp_position(&fixture, &mint, &other).shares;
+    let result = remove(&mut fixture, &other, &mint, other_shares + 1);
+
+    assert_error(result, ErrorCode::InsufficientLiquidity);
+    assert_eq!(lp_position(&fixture, &mint, &other).shares, other_shares);
+}
+
+#[test]
+fn initial_liquidity_is_locked() {
+    let mut fixture = Fixture::new();
+    let (creator, mint) = fixture.create_token();
+    fixture
+        .buy(&creator, &mint, LAMPORTS_PER_SOL / 100)
+        .unwrap();
+
+    // Nobody holds a position for the shares minted at creation
+    let result = remove(&mut fixture, &creator, &mint, 1);
+
+    assert!(result.is_err());
+    assert_eq!(
+        liquidity(&fixture, &mint),
+        CURVE_PARAMS.virtual_sol_reserves
+    );
+}
+
+#[test]
+fn add_liquidity_rejects_linear_curves() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token_with(LINEAR);
+    let (provider, tokens) = provider_with_tokens(&mut fixture, &mint);
+
+    assert_error(
+        add(
+            &mut fixture,
+            &provider,
+            &mint,
+            LAMPORTS_PER_SOL / 1000,
+            tokens,
+        ),
+        ErrorCode::LiquidityUnsupported,
+    );
+}
+
+#[test]
+fn add_liquidity_rejects_exponential_curves() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token_with(EXPONENTIAL);
+    let (provider, tokens) = provider_with_tokens(&mut fixture, &mint);
+
+    assert_error(
+        add(
+            &mut fixture,
+            &provider,
+            &mint,
+            LAMPORTS_PER_SOL / 1000,
+            tokens,
+        ),
+        ErrorCode::LiquidityUnsupported,
+    );
+}
+
+#[test]
+fn constant_product_liquidity_round_trip_keeps_price_and_backing() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token_with(CURVE_PARAMS);
+    let (provider, tokens) = provider_with_tokens(&mut fixture, &mint);
+    let provider_ata = get_associated_token_address(&provider, &mint);
+    let before = fixture.token_config(&mint);
+
+    add(
+        &mut fixture,
+        &provider,
+        &mint,
+        LAMPORTS_PER_SOL / 1000,
+        tokens,
+    )
+    .unwrap();
+
+    // The matched token side rounds up, so the price can only drop, by at
+    // most one base unit's worth
+    let after = fixture.token_config(&mint);
+    let price_before = before.virtual_sol_reserves as u128 * after.virtual_token_reserves as u128;
+    let price_after = after.virtual_sol_reserves as u128 * before.virtual_token_reserves as u128;
+    assert!(price_after <= price_before);
+    assert!(price_before - price_after <= after.virtual_sol_reserves as u128);
+
+    let sol_before_remove = fixture.svm.lamports(&provider);
+    let tokens_before_remove = fixture.svm.token_balance(&provider_ata);
+    let shares = lp_position(&fixture, &mint, &provider).shares;
+    remove(&mut fixture, &provider, &mint, shares).unwrap();
+
+    assert!(fixture.svm.lamports(&provider) - sol_before_remove <= LAMPORTS_PER_SOL / 1000);
+    assert!(fixture.svm.token_balance(&provider_ata) - tokens_before_remove <= tokens);
+
+    // Every token bought from the curve can still be sold back into it
+    let balance = fixture.svm.token_balance(&provider_ata);
+    fixture.sell(&provider, &mint, balance).unwrap();
+}
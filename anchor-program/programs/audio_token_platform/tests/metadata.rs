@@ -0,0 +1,137 @@
+mod common;
+
+use anchor_spl::metadata::mpl_token_metadata::instructions::{
+    UpdateMetadataAccountV2, UpdateMetadataAccountV2InstructionArgs,
+};
+use audio_token_platform::{ErrorCode, TokenMetadataUpdated};
+use common::*;
+
+const NEW_NAME: &str = "Noiz Remix";
+const NEW_SYMBOL: &str = "RMX";
+const NEW_URI: &str = "ipfs://bafybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku";
+
+#[test]
+fn creator_updates_metaplex_and_token_config_together() {
+    let mut fixture = Fixture::new();
+    let (creator, mint) = fixture.create_token();
+
+    fixture
+        .svm
+        .send(
+            &[update_token_metadata_ix(
+                &creator, &mint, NEW_NAME, NEW_SYMBOL, NEW_URI,
+            )],
+            &[&creator],
+        )
+        .unwrap();
+
+    let token_config = fixture.token_config(&mint);
+    assert_eq!(token_config.name, NEW_NAME);
+    assert_eq!(token_config.symbol, NEW_SYMBOL);
+    assert_eq!(token_config.metadata_uri, NEW_URI);
+    let metadata = fixture.svm.metadata(&mint);
+    assert_eq!(metadata.data.name, NEW_NAME);
+    assert_eq!(metadata.data.symbol, NEW_SYMBOL);
+    assert_eq!(metadata.data.uri, NEW_URI);
+    assert!(metadata.is_mutable);
+
+    let event = fixture.svm.events::<TokenMetadataUpdated>().pop().unwrap();
+    assert_eq!(event.mint, mint);
+    assert_eq!(event.name, NEW_NAME);
+}
+
+#[test]
+fn token_config_is_the_update_authority() {
+    let mut fixture = Fixture::new();
+    let (creator, mint) = fixture.create_token();
+
+    assert_eq!(
+        fixture.svm.metadata(&mint).update_authority,
+        token_config_address(&mint)
+    );
+
+    // So the creator can't bypass the program, e.g. to undo `lock_metadata`
+    let bypass = UpdateMetadataAccountV2 {
+        metadata: metadata_address(&mint),
+        update_authority: creator,
+    }
+    .instruction(UpdateMetadataAccountV2InstructionArgs {
+        data: None,
+        new_update_authority: Some(creator),
+        primary_sale_happened: None,
+        is_mutable: None,
+    });
+    assert!(fixture.svm.send(&[bypass], &[&creator]).is_err());
+    assert_eq!(
+        fixture.svm.metadata(&mint).update_authority,
+        token_config_address(&mint)
+    );
+}
+
+#[test]
+fn only_the_creator_can_update_metadata() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let attacker = fixture.funded_wallet(LAMPORTS_PER_SOL);
+
+    let result = fixture.svm.send(
+        &[update_token_metadata_ix(
+            &attacker, &mint, NEW_NAME, NEW_SYMBOL, NEW_URI,
+        )],
+        &[&attacker],
+    );
+    assert_error(result, ErrorCode::Unauthorized);
+    assert_error(
+        fixture
+            .svm
+            .send(&[lock_metadata_ix(&attacker, &mint)], &[&attacker]),
+        ErrorCode::Unauthorized,
+    );
+}
+
+#[test]
+fn update_token_metadata_validates_lengths() {
+    let mut fixture = Fixture::new();
+    let (creator, mint) = fixture.create_token();
+
+    let result = fixture.svm.send(
+        &[update_token_metadata_ix(
+            &creator,
+            &mint,
+            &"n".repeat(33),
+            NEW_SYMBOL,
+            NEW_URI,
+        )],
+        &[&creator],
+    );
+
+    assert_error(result, ErrorCode::InvalidName);
+}
+
+#[test]
+fn locked_metadata_cannot_change() {
+    let mut fixture = Fixture::new();
+    let (creator, mint) = fixture.create_token();
+
+    fixture
+        .svm
+        .send(&[lock_metadata_ix(&creator, &mint)], &[&creator])
+        .unwrap();
+    assert!(fixture.token_config(&mint).metadata_locked);
+    assert!(!fixture.svm.metadata(&mint).is_mutable);
+
+    let result = fixture.svm.send(
+        &[update_token_metadata_ix(
+            &creator, &mint, NEW_NAME, NEW_SYMBOL, NEW_URI,
+        )],
+        &[&creator],
+    );
+    assert_error(result, ErrorCode::MetadataLocked);
+    assert_error(
+        fixture
+            .svm
+            .send(&[lock_metadata_ix(&creator, &mint)], &[&creator]),
+        ErrorCode::MetadataLocked,
+    );
+    assert_eq!(fixture.token_config(&mint).name, "Noiz Sound");
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address;
+use audio_token_platform::{ErrorCode, UpdatePlatformConfigParams};
+use common::*;
+
+/// A wallet holding tokens and LP shares, so every trading instruction has
+/// something to act on.
+fn trader_with_position(fixture: &mut Fixture, mint: &Pubkey) -> (Pubkey, u64) {
+    let trader = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+    fixture.buy(&trader, mint, LAMPORTS_PER_SOL / 100).unwrap();
+    let tokens = fixture
+        .svm
+        .token_balance(&get_associated_token_address(&trader, mint));
+    fixture
+        .svm
+        .send(
+            &[add_liquidity_ix(
+                &trader,
+                mint,
+                LAMPORTS_PER_SOL,
+                tokens / 2,
+                0,
+            )],
+            &[&trader],
+        )
+        .unwrap();
+    (trader, tokens / 4)
+}
+
+fn set_halted(fixture: &mut Fixture, mint: &Pubkey, halted: bool) {
+    let admin = fixture.admin;
+    fixture
+        .svm
+        .send(&[set_trading_halted_ix(&admin, mint, halted)], &[&admin])
+        .unwrap();
+}
+
+/// Every trading and liquidity instruction fails with `TradingPaused`.
+fn assert_all_paused(fixture: &mut Fixture, trader: &Pubkey, mint: &Pubkey, tokens: u64) {
+    assert_error(
+        fixture.buy(trader, mint, LAMPORTS_PER_SOL / 100),
+        ErrorCode::TradingPaused,
+    );
+    assert_error(fixture.sell(trader, mint, tokens), ErrorCode::TradingPaused);
+    let add = add_liquidity_ix(trader, mint, LAMPORTS_PER_SOL, tokens, 0);
+    assert_error(
+        fixture.svm.send(&[add], &[trader]),
+        ErrorCode::TradingPaused,
+    );
+    let remove = remove_liquidity_ix(trader, mint, 1, 0, 0);
+    assert_error(
+        fixture.svm.send(&[remove], &[trader]),
+        ErrorCode::TradingPaused,
+    );
+}
+
+#[test]
+fn global_pause_stops_trading_and_liquidity() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let (trader, tokens) = trader_with_position(&mut fixture, &mint);
+
+    fixture.update_platform_config(UpdatePlatformConfigParams {
+        trading_paused: Some(true),
+        ..Default::default()
+    });
+    assert_all_paused(&mut fixture, &trader, &mint, tokens);
+
+    fixture.update_platform_config(UpdatePlatformConfigParams {
+        trading_paused: Some(false),
+        ..Default::default()
+    });
+    fixture.sell(&trader, &mint, tokens).unwrap();
+}
+
+#[test]
+fn halted_token_stops_only_that_token() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let (_, other_mint) = fixture.create_token();
+    let (trader, tokens) = trader_with_position(&mut fixture, &mint);
+
+    set_halted(&mut fixture, &mint, true);
+    assert!(fixture.token_config(&mint).trading_halted);
+    assert_all_paused(&mut fixture, &trader, &mint, tokens);
+    fixture
+        .buy(&trader, &other_mint, LAMPORTS_PER_SOL / 100)
+        .unwrap();
+
+    set_halted(&mut fixture, &mint, false);
+    fixture.sell(&trader, &mint, tokens).unwrap();
+}
+
+#[test]
+fn only_the_admin_can_halt_a_token() {
+    let mut fixture = Fixture::new();
+    let (creator, mint) = fixture.create_token();
+
+    let result = fixture
+        .svm
+        .send(&[set_trading_halted_ix(&creator, &mint, true)], &[&creator]);
+
+    assert_error(result, ErrorCode::Unauthorized);
+    assert!(!fixture.token_config(&mint).trading_halted);
+}
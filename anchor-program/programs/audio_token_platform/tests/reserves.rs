@@ -0,0 +1,117 @@
+mod common;
+
+use anchor_lang::prelude::{Pubkey, Rent};
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::spl_token;
+use audio_token_platform::{ErrorCode, TokenConfig};
+use common::*;
+
+fn token_config_rent() -> u64 {
+    Rent::default().minimum_balance(8 + <TokenConfig as anchor_lang::Space>::INIT_SPACE)
+}
+
+/// The curve PDA holds exactly its rent plus every lamport of real reserves.
+fn assert_fully_backed(fixture: &Fixture, mint: &Pubkey) {
+    let token_config = fixture.token_config(mint);
+    assert_eq!(
+        fixture.svm.lamports(&token_config_address(mint)),
+        token_config_rent() + token_config.real_sol_reserves
+    );
+}
+
+#[test]
+fn new_curve_has_only_virtual_sol() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+
+    let token_config = fixture.token_config(&mint);
+    assert_eq!(
+        token_config.virtual_sol_reserves,
+        CURVE_PARAMS.virtual_sol_reserves
+    );
+    assert_eq!(token_config.real_sol_reserves, 0);
+    assert_fully_backed(&fixture, &mint);
+}
+
+#[test]
+fn buy_credits_only_post_fee_sol_to_reserves() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let trader = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+
+    let sol_amount = LAMPORTS_PER_SOL / 10;
+    fixture.buy(&trader, &mint, sol_amount).unwrap();
+
+    let sol_to_curve = sol_amount - sol_amount * TRADE_FEE_BPS / 10_000;
+    let token_config = fixture.token_config(&mint);
+    assert_eq!(token_config.real_sol_reserves, sol_to_curve);
+    assert_eq!(
+        token_config.virtual_sol_reserves,
+        CURVE_PARAMS.virtual_sol_reserves + sol_to_curve
+    );
+    assert_fully_backed(&fixture, &mint);
+}
+
+#[test]
+fn sell_pays_proceeds_and_fee_out_of_real_reserves() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let trader = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+    let trader_ata = get_associated_token_address(&trader, &mint);
+    fixture.buy(&trader, &mint, LAMPORTS_PER_SOL / 10).unwrap();
+
+    let real_before = fixture.token_config(&mint).real_sol_reserves;
+    let trader_before = fixture.svm.lamports(&trader);
+    let treasury_before = fixture.svm.lamports(&fixture.treasury);
+    let balance = fixture.svm.token_balance(&trader_ata);
+    fixture.sell(&trader, &mint, balance).unwrap();
+
+    let sol_out = (fixture.svm.lamports(&trader) - trader_before)
+        + (fixture.svm.lamports(&fixture.treasury) - treasury_before);
+    let token_config = fixture.token_config(&mint);
+    assert!(sol_out > 0);
+    assert_eq!(token_config.real_sol_reserves, real_before - sol_out);
+    assert_fully_backed(&fixture, &mint);
+}
+
+#[test]
+fn sell_cannot_pay_out_more_than_real_reserves() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let trader = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+    fixture.buy(&trader, &mint, LAMPORTS_PER_SOL / 100).unwrap();
+
+    // Tokens that never came from the curve, priced against the virtual
+    // reserves, would be worth more than the curve actually holds. The mint
+    // authority is revoked, so give the trader a forged balance instead.
+    let trader_ata = get_associated_token_address(&trader, &mint);
+    let mut account = fixture.svm.account(&trader_ata).unwrap().clone();
+    let mut token_account = spl_token::state::Account::unpack(&account.data).unwrap();
+    token_account.amount = TOTAL_SUPPLY / 2;
+    spl_token::state::Account::pack(token_account, &mut account.data).unwrap();
+    fixture.svm.set_account(trader_ata, account);
+
+    let result = fixture.sell(&trader, &mint, TOTAL_SUPPLY / 2);
+
+    assert_error(result, ErrorCode::InsufficientLiquidity);
+    assert_fully_backed(&fixture, &mint);
+}
+
+#[test]
+fn trades_fail_when_curve_lamports_do_not_cover_reserves() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let trader = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+    fixture.buy(&trader, &mint, LAMPORTS_PER_SOL / 10).unwrap();
+
+    let token_config_key = token_config_address(&mint);
+    let mut account = fixture.svm.account(&token_config_key).unwrap().clone();
+    account.lamports -= 1;
+    fixture.svm.set_account(token_config_key, account);
+
+    assert_error(
+        fixture.buy(&trader, &mint, LAMPORTS_PER_SOL / 100),
+        ErrorCode::ReserveInvariantViolated,
+    );
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::associated_token::{get_associated_token_address, spl_associated_token_account};
+use anchor_spl::token::spl_token;
+use audio_token_platform::TokenCreated;
+use common::*;
+
+#[test]
+fn full_supply_is_minted_into_program_vaults() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+
+    assert_eq!(fixture.svm.mint(&mint).supply, TOTAL_SUPPLY);
+    assert_eq!(
+        fixture.svm.token_balance(&reserve_address(&mint)),
+        TOTAL_SUPPLY / 10
+    );
+    assert_eq!(
+        fixture.svm.token_balance(&creator_vault_address(&mint)),
+        TOTAL_SUPPLY / 20
+    );
+    assert_eq!(
+        fixture.svm.token_balance(&locked_vault_address(&mint)),
+        TOTAL_SUPPLY - TOTAL_SUPPLY / 10 - TOTAL_SUPPLY / 20
+    );
+
+    let event = &fixture.svm.events::<TokenCreated>()[0];
+    assert_eq!(event.creator_allocation, TOTAL_SUPPLY / 20);
+    assert_eq!(
+        event.locked_allocation,
+        TOTAL_SUPPLY - TOTAL_SUPPLY / 10 - TOTAL_SUPPLY / 20
+    );
+}
+
+#[test]
+fn mint_and_freeze_authorities_are_revoked() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+
+    let mint_state = fixture.svm.mint(&mint);
+    assert_eq!(mint_state.mint_authority, COption::None);
+    assert_eq!(mint_state.freeze_authority, COption::None);
+}
+
+#[test]
+fn creator_cannot_mint_after_launch() {
+    let mut fixture = Fixture::new();
+    let (creator, mint) = fixture.create_token();
+    let creator_ata = get_associated_token_address(&creator, &mint);
+
+    let result = fixture.svm.send(
+        &[
+            spl_associated_token_account::instruction::create_associated_token_account(
+                &creator,
+                &creator,
+                &mint,
+                &spl_token::ID,
+            ),
+            spl_token::instruction::mint_to(
+                &spl_token::ID,
+                &mint,
+                &creator_ata,
+                &creator,
+                &[],
+                TOTAL_SUPPLY,
+            )
+            .unwrap(),
+        ],
+        &[&creator],
+    );
+
+    assert!(result.is_err());
+    assert_eq!(fixture.svm.mint(&mint).supply, TOTAL_SUPPLY);
+}
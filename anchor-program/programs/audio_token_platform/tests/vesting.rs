@@ -0,0 +1,160 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address;
+use audio_token_platform::{
+    ErrorCode, VestedTokensClaimed, VestingSchedule, VESTING_CLAIM_INTERVAL_SECONDS,
+    VESTING_DURATION_SECONDS,
+};
+use common::*;
+
+const CREATOR_ALLOCATION: u64 = TOTAL_SUPPLY / 20;
+
+fn vesting_schedule(fixture: &Fixture, mint: &Pubkey) -> VestingSchedule {
+    fixture.svm.anchor_account(&vesting_schedule_address(mint))
+}
+
+fn claim(
+    fixture: &mut Fixture,
+    beneficiary: &Pubkey,
+    mint: &Pubkey,
+) -> std::result::Result<(), TxError> {
+    let ix = claim_vested_ix(beneficiary, mint);
+    fixture.svm.send(&[ix], &[beneficiary])
+}
+
+#[test]
+fn create_audio_token_creates_vesting_schedule() {
+    let mut fixture = Fixture::new();
+    let (creator, mint) = fixture.create_token();
+
+    let schedule = vesting_schedule(&fixture, &mint);
+    assert_eq!(schedule.mint, mint);
+    assert_eq!(schedule.beneficiary, creator);
+    assert_eq!(schedule.start_time, fixture.token_config(&mint).created_at);
+    assert_eq!(schedule.duration_seconds, VESTING_DURATION_SECONDS);
+    assert_eq!(
+        schedule.claim_interval_seconds,
+        VESTING_CLAIM_INTERVAL_SECONDS
+    );
+    assert_eq!(schedule.total_amount, CREATOR_ALLOCATION);
+    assert_eq!(schedule.claimed_amount, 0);
+}
+
+#[test]
+fn allocation_is_locked_until_graduation() {
+    let mut fixture = Fixture::new();
+    let (creator, mint) = fixture.create_token();
+
+    fixture.svm.advance_time(VESTING_DURATION_SECONDS);
+    assert_error(
+        claim(&mut fixture, &creator, &mint),
+        ErrorCode::AllocationLocked,
+    );
+    assert_eq!(
+        fixture.svm.token_balance(&creator_vault_address(&mint)),
+        CREATOR_ALLOCATION
+    );
+}
+
+#[test]
+fn creator_cannot_drain_the_curve() {
+    let mut fixture = Fixture::new();
+    let (creator, mint) = fixture.create_token();
+    let buyer = fixture.funded_wallet(10 * LAMPORTS_PER_SOL);
+    fixture
+        .buy(&buyer, &mint, GRADUATION_THRESHOLD / 2)
+        .unwrap();
+    let real_sol_reserves = fixture.token_config(&mint).real_sol_reserves;
+
+    // Fully vested, but still locked while the curve trades, so the creator
+    // holds nothing to sell into it
+    fixture.svm.advance_time(VESTING_DURATION_SECONDS);
+    assert_error(
+        claim(&mut fixture, &creator, &mint),
+        ErrorCode::AllocationLocked,
+    );
+    assert!(fixture.sell(&creator, &mint, CREATOR_ALLOCATION).is_err());
+    assert_eq!(
+        fixture.token_config(&mint).real_sol_reserves,
+        real_sol_reserves
+    );
+}
+
+#[test]
+fn nothing_is_claimable_at_creation() {
+    let mut fixture = Fixture::new();
+    let (creator, mint) = fixture.create_token();
+    fixture.graduate(&mint);
+
+    assert_error(
+        claim(&mut fixture, &creator, &mint),
+        ErrorCode::NothingToClaim,
+    );
+}
+
+#[test]
+fn claims_release_linearly_vested_tokens() {
+    let mut fixture = Fixture::new();
+    let (creator, mint) = fixture.create_token();
+    fixture.graduate(&mint);
+    let creator_ata = get_associated_token_address(&creator, &mint);
+
+    fixture.svm.advance_time(VESTING_DURATION_SECONDS / 4);
+    claim(&mut fixture, &creator, &mint).unwrap();
+    assert_eq!(
+        fixture.svm.token_balance(&creator_ata),
+        CREATOR_ALLOCATION / 4
+    );
+
+    let event = fixture.svm.events::<VestedTokensClaimed>().pop().unwrap();
+    assert_eq!(event.amount, CREATOR_ALLOCATION / 4);
+    assert_eq!(event.claimed_amount, CREATOR_ALLOCATION / 4);
+
+    fixture.svm.advance_time(VESTING_DURATION_SECONDS);
+    claim(&mut fixture, &creator, &mint).unwrap();
+    assert_eq!(fixture.svm.token_balance(&creator_ata), CREATOR_ALLOCATION);
+    assert_eq!(fixture.svm.token_balance(&creator_vault_address(&mint)), 0);
+    assert_eq!(
+        vesting_schedule(&fixture, &mint).claimed_amount,
+        CREATOR_ALLOCATION
+    );
+
+    fixture.svm.advance_time(VESTING_CLAIM_INTERVAL_SECONDS);
+    assert_error(
+        claim(&mut fixture, &creator, &mint),
+        ErrorCode::NothingToClaim,
+    );
+}
+
+#[test]
+fn claims_are_limited_to_one_per_interval() {
+    let mut fixture = Fixture::new();
+    let (creator, mint) = fixture.create_token();
+    fixture.graduate(&mint);
+
+    fixture.svm.advance_time(VESTING_DURATION_SECONDS / 4);
+    claim(&mut fixture, &creator, &mint).unwrap();
+
+    fixture.svm.advance_time(VESTING_CLAIM_INTERVAL_SECONDS - 1);
+    assert_error(
+        claim(&mut fixture, &creator, &mint),
+        ErrorCode::ClaimIntervalNotElapsed,
+    );
+
+    fixture.svm.advance_time(1);
+    claim(&mut fixture, &creator, &mint).unwrap();
+}
+
+#[test]
+fn only_the_creator_can_claim() {
+    let mut fixture = Fixture::new();
+    let (_, mint) = fixture.create_token();
+    let attacker = fixture.funded_wallet(LAMPORTS_PER_SOL);
+
+    fixture.svm.advance_time(VESTING_DURATION_SECONDS);
+    assert_error(
+        claim(&mut fixture, &attacker, &mint),
+        ErrorCode::Unauthorized,
+    );
+}
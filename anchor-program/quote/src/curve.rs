@@ -0,0 +1,448 @@
+//! Bonding curve pricing.
+//!
+//! Every curve starts at a spot price of
+//! `initial_virtual_sol_reserves / initial_virtual_token_reserves`:
+//!
+//! - `ConstantProduct`: `x * y = k` over the current virtual reserves.
+//! - `Linear`: the price grows linearly with the tokens sold and doubles
+//!   after `initial_virtual_token_reserves` have been sold.
+//! - `Exponential`: the price grows by a factor of `e` every
+//!   `initial_virtual_token_reserves` tokens sold.
+//!
+//! Linear and exponential curves are priced on `tokens_sold` alone, so
+//! their reserves say nothing about what an LP share is worth; the program
+//! only accepts liquidity on constant product curves.
+//!
+//! Quotes round in favour of the pool: buys round the tokens out down and
+//! sells round the SOL out down, so a buy followed by a sell of the same
+//! tokens never returns more SOL than was paid in.
+
+use crate::math::{div_ceil, exp_wad_down, exp_wad_up, isqrt, ln_wad_down, WAD};
+use crate::QuoteError;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CurveType {
+    ConstantProduct,
+    Linear,
+    Exponential,
+}
+
+/// Snapshot of the curve state a quote is priced against.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Curve {
+    pub curve_type: CurveType,
+    pub initial_virtual_sol_reserves: u64,
+    pub initial_virtual_token_reserves: u64,
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub tokens_sold: u64,
+}
+
+impl Curve {
+    /// Tokens received for `sol_in` lamports reaching the curve (after fees).
+    pub fn quote_buy(&self, sol_in: u64) -> Result<u64, QuoteError> {
+        let tokens_out = match self.curve_type {
+            CurveType::ConstantProduct => {
+                // Round the remaining token reserve up
+                let k = self.virtual_sol_reserves as u128 * self.virtual_token_reserves as u128;
+                let new_virtual_sol_reserves = self.virtual_sol_reserves as u128 + sol_in as u128;
+                let new_virtual_token_reserves =
+                    div_ceil(k, new_virtual_sol_reserves).ok_or(QuoteError::MathOverflow)?;
+                (self.virtual_token_reserves as u128)
+                    .checked_sub(new_virtual_token_reserves)
+                    .ok_or(QuoteError::InsufficientLiquidity)?
+            }
+            CurveType::Linear => {
+                // Cost of moving from u0 to u1 (u = scale + tokens_sold) is
+                // sol0 * (u1^2 - u0^2) / (2 * scale^2), solved for u1
+                let (sol0, scale) = self.initial();
+                let u0 = scale + self.tokens_sold as u128;
+                let delta = (scale * sol_in as u128 / sol0)
+                    .checked_mul(2 * scale)
+                    .ok_or(QuoteError::MathOverflow)?;
+                let u1 = u0
+                    .checked_mul(u0)
+                    .and_then(|v| v.checked_add(delta))
+                    .map(isqrt)
+                    .ok_or(QuoteError::MathOverflow)?;
+                u1 - u0
+            }
+            CurveType::Exponential => {
+                // Cost of moving from s0 to s1 is sol0 * (e^(s1/scale) - e^(s0/scale)),
+                // solved for s1
+                let (sol0, scale) = self.initial();
+                let e0 = exp_wad_down(self.tokens_sold as u128 * WAD / scale)
+                    .ok_or(QuoteError::MathOverflow)?;
+                let e1 = (sol_in as u128 * WAD / sol0)
+                    .checked_add(e0)
+                    .ok_or(QuoteError::MathOverflow)?;
+                let s1 = ln_wad_down(e1)
+                    .checked_mul(scale)
+                    .ok_or(QuoteError::MathOverflow)?
+                    / WAD;
+                s1.saturating_sub(self.tokens_sold as u128)
+            }
+        };
+        to_u64(tokens_out)
+    }
+
+    /// Lamports released by the curve for `token_amount` tokens (before fees).
+    pub fn quote_sell(&self, token_amount: u64) -> Result<u64, QuoteError> {
+        let sol_out = match self.curve_type {
+            CurveType::ConstantProduct => {
+                // Round the remaining SOL reserve up
+                let k = self.virtual_sol_reserves as u128 * self.virtual_token_reserves as u128;
+                let new_virtual_token_reserves =
+                    self.virtual_token_reserves as u128 + token_amount as u128;
+                let new_virtual_sol_reserves =
+                    div_ceil(k, new_virtual_token_reserves).ok_or(QuoteError::MathOverflow)?;
+                (self.virtual_sol_reserves as u128)
+                    .checked_sub(new_virtual_sol_reserves)
+                    .ok_or(QuoteError::InsufficientLiquidity)?
+            }
+            CurveType::Linear => {
+                ensure!(
+                    token_amount <= self.tokens_sold,
+                    QuoteError::InsufficientLiquidity
+                );
+                let (sol0, scale) = self.initial();
+                let u0 = scale + self.tokens_sold as u128;
+                let u1 = u0 - token_amount as u128;
+                (token_amount as u128 * (u0 + u1) / scale)
+                    .checked_mul(sol0)
+                    .ok_or(QuoteError::MathOverflow)?
+                    / (2 * scale)
+            }
+            CurveType::Exponential => {
+                // Bound e0 from below and e1 from above
+                ensure!(
+                    token_amount <= self.tokens_sold,
+                    QuoteError::InsufficientLiquidity
+                );
+                let (sol0, scale) = self.initial();
+                let e0 = exp_wad_down(self.tokens_sold as u128 * WAD / scale)
+                    .ok_or(QuoteError::MathOverflow)?;
+                let remaining = (self.tokens_sold - token_amount) as u128;
+                let e1 = div_ceil(remaining * WAD, scale)
+                    .and_then(exp_wad_up)
+                    .ok_or(QuoteError::MathOverflow)?;
+                e0.saturating_sub(e1)
+                    .checked_mul(sol0)
+                    .ok_or(QuoteError::MathOverflow)?
+                    / WAD
+            }
+        };
+        to_u64(sol_out)
+    }
+
+    /// Lamports that must reach the curve (after fees) to buy exactly
+    /// `token_amount` tokens, rounded up.
+    pub fn quote_buy_exact_tokens(&self, token_amount: u64) -> Result<u64, QuoteError> {
+        let sol_in = match self.curve_type {
+            CurveType::ConstantProduct => {
+                ensure!(
+                    token_amount < self.virtual_token_reserves,
+                    QuoteError::InsufficientLiquidity
+                );
+                let k = self.virtual_sol_reserves as u128 * self.virtual_token_reserves as u128;
+                let new_virtual_token_reserves =
+                    (self.virtual_token_reserves - token_amount) as u128;
+                let new_virtual_sol_reserves =
+                    div_ceil(k, new_virtual_token_reserves).ok_or(QuoteError::MathOverflow)?;
+                new_virtual_sol_reserves - self.virtual_sol_reserves as u128
+            }
+            CurveType::Linear => {
+                let (sol0, scale) = self.initial();
+                let u0 = scale + self.tokens_sold as u128;
+                let u1 = u0 + token_amount as u128;
+                div_ceil(token_amount as u128 * (u0 + u1), scale)
+                    .and_then(|v| v.checked_mul(sol0))
+                    .and_then(|v| div_ceil(v, 2 * scale))
+                    .ok_or(QuoteError::MathOverflow)?
+            }
+            CurveType::Exponential => {
+                // Bound e1 from above and e0 from below
+                let (sol0, scale) = self.initial();
+                let e0 = exp_wad_down(self.tokens_sold as u128 * WAD / scale)
+                    .ok_or(QuoteError::MathOverflow)?;
+                let sold_after = self.tokens_sold as u128 + token_amount as u128;
+                let e1 = div_ceil(sold_after * WAD, scale)
+                    .and_then(exp_wad_up)
+                    .ok_or(QuoteError::MathOverflow)?;
+                (e1 - e0)
+                    .checked_mul(sol0)
+                    .and_then(|v| div_ceil(v, WAD))
+                    .ok_or(QuoteError::MathOverflow)?
+            }
+        };
+        to_u64(sol_in)
+    }
+
+    /// Tokens that must be sold for the curve to release exactly `sol_out`
+    /// lamports (before fees), rounded up.
+    pub fn quote_sell_exact_sol(&self, sol_out: u64) -> Result<u64, QuoteError> {
+        let tokens_in = match self.curve_type {
+            CurveType::ConstantProduct => {
+                ensure!(
+                    sol_out < self.virtual_sol_reserves,
+                    QuoteError::InsufficientLiquidity
+                );
+                let k = self.virtual_sol_reserves as u128 * self.virtual_token_reserves as u128;
+                let new_virtual_sol_reserves = (self.virtual_sol_reserves - sol_out) as u128;
+                let new_virtual_token_reserves =
+                    div_ceil(k, new_virtual_sol_reserves).ok_or(QuoteError::MathOverflow)?;
+                new_virtual_token_reserves - self.virtual_token_reserves as u128
+            }
+            CurveType::Linear => {
+                // Solve sol0 * (u0^2 - u1^2) / (2 * scale^2) = sol_out for u1,
+                // rounding u1 down
+                let (sol0, scale) = self.initial();
+                let u0 = scale + self.tokens_sold as u128;
+                let delta = div_ceil(scale * sol_out as u128, sol0)
+                    .and_then(|v| v.checked_mul(2 * scale))
+                    .ok_or(QuoteError::MathOverflow)?;
+                let u1 = u0
+                    .checked_mul(u0)
+                    .ok_or(QuoteError::MathOverflow)?
+                    .checked_sub(delta)
+                    .map(isqrt)
+                    .ok_or(QuoteError::InsufficientLiquidity)?;
+                ensure!(u1 >= scale, QuoteError::InsufficientLiquidity);
+                u0 - u1
+            }
+            CurveType::Exponential => {
+                // Solve sol0 * (e^(s0/scale) - e^(s1/scale)) = sol_out for s1,
+                // rounding s1 down
+                let (sol0, scale) = self.initial();
+                let e0 = exp_wad_down(self.tokens_sold as u128 * WAD / scale)
+                    .ok_or(QuoteError::MathOverflow)?;
+                let e1 = div_ceil(sol_out as u128 * WAD, sol0)
+                    .and_then(|v| e0.checked_sub(v))
+                    .filter(|e1| *e1 >= WAD)
+                    .ok_or(QuoteError::InsufficientLiquidity)?;
+                let s1 = ln_wad_down(e1)
+                    .checked_mul(scale)
+                    .ok_or(QuoteError::MathOverflow)?
+                    / WAD;
+                (self.tokens_sold as u128).saturating_sub(s1)
+            }
+        };
+        to_u64(tokens_in)
+    }
+
+    /// Marginal price in lamports per token base unit, scaled by `WAD` and
+    /// rounded down. Since SOL and the tokens both have 9 decimals, this is
+    /// also the price in SOL per whole token.
+    pub fn spot_price(&self) -> Result<u128, QuoteError> {
+        match self.curve_type {
+            CurveType::ConstantProduct => (self.virtual_sol_reserves as u128 * WAD)
+                .checked_div(self.virtual_token_reserves as u128)
+                .ok_or(QuoteError::MathOverflow),
+            CurveType::Linear => {
+                // Derivative of the cost: sol0 * u / scale^2
+                let (sol0, scale) = self.initial();
+                let u = scale + self.tokens_sold as u128;
+                (sol0 * WAD / scale)
+                    .checked_mul(u)
+                    .ok_or(QuoteError::MathOverflow)
+                    .map(|v| v / scale)
+            }
+            CurveType::Exponential => {
+                // Derivative of the cost: sol0 / scale * e^(s/scale)
+                let (sol0, scale) = self.initial();
+                exp_wad_down(self.tokens_sold as u128 * WAD / scale)
+                    .and_then(|e| e.checked_mul(sol0))
+                    .ok_or(QuoteError::MathOverflow)
+                    .map(|v| v / scale)
+            }
+        }
+    }
+
+    fn initial(&self) -> (u128, u128) {
+        (
+            self.initial_virtual_sol_reserves as u128,
+            self.initial_virtual_token_reserves as u128,
+        )
+    }
+}
+
+fn to_u64(value: u128) -> Result<u64, QuoteError> {
+    u64::try_from(value).map_err(|_| QuoteError::MathOverflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const SOL: u64 = 1_000_000_000;
+    const TOKEN: u64 = 1_000_000_000;
+
+    fn curve_type() -> impl Strategy<Value = CurveType> {
+        prop_oneof![
+            Just(CurveType::ConstantProduct),
+            Just(CurveType::Linear),
+            Just(CurveType::Exponential),
+        ]
+    }
+
+    /// Curves with realistic parameters: up to 1B tokens and 1000 SOL of
+    /// virtual reserves, and a position after some buys.
+    fn curve() -> impl Strategy<Value = Curve> {
+        (
+            curve_type(),
+            1..1_000 * SOL,
+            1_000 * TOKEN..1_000_000_000 * TOKEN,
+            0..100 * SOL,
+        )
+            .prop_map(|(curve_type, sol0, tokens0, bought_with)| {
+                let mut curve = Curve {
+                    curve_type,
+                    initial_virtual_sol_reserves: sol0,
+                    initial_virtual_token_reserves: tokens0,
+                    virtual_sol_reserves: sol0,
+                    virtual_token_reserves: tokens0,
+                    tokens_sold: 0,
+                };
+                if let Ok(tokens) = curve.quote_buy(bought_with) {
+                    curve = curve.after_buy(bought_with, tokens);
+                }
+                curve
+            })
+    }
+
+    impl Curve {
+        fn after_sell(mut self, token_amount: u64, sol_out: u64) -> Self {
+            self.virtual_sol_reserves -= sol_out;
+            self.virtual_token_reserves += token_amount;
+            self.tokens_sold -= token_amount;
+            self
+        }
+
+        fn after_buy(mut self, sol_in: u64, tokens_out: u64) -> Self {
+            self.virtual_sol_reserves += sol_in;
+            // Linear and exponential quotes aren't bounded by the virtual
+            // token reserves; on-chain the real reserves bound them instead
+            self.virtual_token_reserves = self.virtual_token_reserves.saturating_sub(tokens_out);
+            self.tokens_sold += tokens_out;
+            self
+        }
+    }
+
+    #[test]
+    fn realistic_supply_does_not_overflow() {
+        let curve = Curve {
+            curve_type: CurveType::ConstantProduct,
+            initial_virtual_sol_reserves: 30 * SOL,
+            initial_virtual_token_reserves: 1_073_000_000 * TOKEN,
+            virtual_sol_reserves: 30 * SOL,
+            virtual_token_reserves: 1_073_000_000 * TOKEN,
+            tokens_sold: 0,
+        };
+        let tokens = curve.quote_buy(85 * SOL).unwrap();
+        // 1.073B * 85 / 115 tokens, rounded down
+        assert_eq!(tokens, 793_086_956_521_739_130);
+        let sol = curve
+            .after_buy(85 * SOL, tokens)
+            .quote_sell(tokens)
+            .unwrap();
+        assert!(sol <= 85 * SOL);
+        assert!(85 * SOL - sol <= 1);
+    }
+
+    proptest! {
+        #[test]
+        fn buy_then_sell_never_profits(curve in curve(), sol_in in 0..100 * SOL) {
+            let Ok(tokens) = curve.quote_buy(sol_in) else { return Ok(()) };
+            let sol_out = curve.after_buy(sol_in, tokens).quote_sell(tokens).unwrap();
+            prop_assert!(sol_out <= sol_in, "{:?}: paid {} got {}", curve.curve_type, sol_in, sol_out);
+        }
+
+        #[test]
+        fn buys_are_monotonic(curve in curve(), a in 0..100 * SOL, b in 0..100 * SOL) {
+            let (small, large) = (a.min(b), a.max(b));
+            if let (Ok(few), Ok(many)) = (curve.quote_buy(small), curve.quote_buy(large)) {
+                prop_assert!(few <= many);
+            }
+        }
+
+        #[test]
+        fn sells_are_monotonic(curve in curve(), a: u64, b: u64) {
+            let (small, large) = (a.min(b) % (curve.tokens_sold + 1), a.max(b) % (curve.tokens_sold + 1));
+            let (small, large) = (small.min(large), small.max(large));
+            let few = curve.quote_sell(small).unwrap();
+            let many = curve.quote_sell(large).unwrap();
+            prop_assert!(few <= many);
+        }
+
+        #[test]
+        fn splitting_a_buy_never_beats_a_single_buy(curve in curve(), a in 0..50 * SOL, b in 0..50 * SOL) {
+            let Ok(whole) = curve.quote_buy(a + b) else { return Ok(()) };
+            let first = curve.quote_buy(a).unwrap();
+            let Ok(second) = curve.after_buy(a, first).quote_buy(b) else { return Ok(()) };
+            prop_assert!(first + second <= whole + 1);
+        }
+
+        #[test]
+        fn exact_buy_then_sell_never_profits(curve in curve(), token_amount in 1..100_000_000 * TOKEN) {
+            let Ok(sol_in) = curve.quote_buy_exact_tokens(token_amount) else { return Ok(()) };
+            let Ok(sol_out) = curve.after_buy(sol_in, token_amount).quote_sell(token_amount) else { return Ok(()) };
+            prop_assert!(sol_out <= sol_in, "{:?}: paid {} got {}", curve.curve_type, sol_in, sol_out);
+        }
+
+        #[test]
+        fn exact_sell_then_buy_back_never_profits(curve in curve(), sol_out in 1..100 * SOL) {
+            let Ok(tokens_in) = curve.quote_sell_exact_sol(sol_out) else { return Ok(()) };
+            prop_assume!(tokens_in <= curve.tokens_sold && sol_out < curve.virtual_sol_reserves);
+            let after = curve.after_sell(tokens_in, sol_out);
+            let sol_back = after.quote_buy_exact_tokens(tokens_in).unwrap();
+            prop_assert!(sol_back >= sol_out, "{:?}: got {} rebought for {}", curve.curve_type, sol_out, sol_back);
+        }
+
+        #[test]
+        fn exact_buy_costs_no_more_than_the_matching_buy(curve in curve(), sol_in in 1..100 * SOL) {
+            let Ok(tokens) = curve.quote_buy(sol_in) else { return Ok(()) };
+            prop_assume!(tokens > 0);
+            let Ok(exact) = curve.quote_buy_exact_tokens(tokens) else { return Ok(()) };
+            // The exact-out quote rounds up twice where the buy rounds down once
+            prop_assert!(exact <= sol_in + 2, "{:?}: {} > {}", curve.curve_type, exact, sol_in);
+        }
+
+        #[test]
+        fn trade_sequences_never_drain_the_curve(
+            curve in curve(),
+            trades in prop::collection::vec((any::<bool>(), 0..10 * SOL, 1..=10_000u64), 1..50),
+        ) {
+            let mut current = curve;
+            for (is_buy, sol_in, sell_bps) in trades {
+                if is_buy {
+                    let Ok(tokens) = current.quote_buy(sol_in) else { continue };
+                    current = current.after_buy(sol_in, tokens);
+                } else {
+                    let token_amount = (current.tokens_sold as u128 * sell_bps as u128 / 10_000) as u64;
+                    let sol_out = current.quote_sell(token_amount).unwrap();
+                    prop_assert!(sol_out <= current.virtual_sol_reserves - current.initial_virtual_sol_reserves);
+                    current = current.after_sell(token_amount, sol_out);
+                }
+            }
+            // Whatever the order of trades, selling everything back never
+            // pays out more than the curve took in
+            let sol_out = current.quote_sell(current.tokens_sold).unwrap();
+            prop_assert!(
+                sol_out <= current.virtual_sol_reserves - current.initial_virtual_sol_reserves,
+                "{:?}: owes {} holds {}", current.curve_type, sol_out,
+                current.virtual_sol_reserves - current.initial_virtual_sol_reserves
+            );
+        }
+
+        #[test]
+        fn constant_product_k_never_decreases(curve in curve(), sol_in in 0..100 * SOL) {
+            prop_assume!(curve.curve_type == CurveType::ConstantProduct);
+            let k = curve.virtual_sol_reserves as u128 * curve.virtual_token_reserves as u128;
+            let tokens = curve.quote_buy(sol_in).unwrap();
+            let after = curve.after_buy(sol_in, tokens);
+            let k_after = after.virtual_sol_reserves as u128 * after.virtual_token_reserves as u128;
+            prop_assert!(k_after >= k);
+        }
+    }
+}
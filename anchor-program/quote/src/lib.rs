@@ -0,0 +1,359 @@
+//! Bonding curve quotes shared by the on-chain program, the Rust client and
+//! the web app.
+//!
+//! The program prices every trade with these functions, so a quote computed
+//! here from the current `PlatformConfig` and `TokenConfig` is exactly what
+//! the trade instruction settles. The crate is `no_std`; the `wasm` feature
+//! adds JavaScript bindings (see [`wasm`]).
+
+#![cfg_attr(not(any(test, feature = "wasm")), no_std)]
+
+/// Returns `$err` unless `$cond` holds.
+macro_rules! ensure {
+    ($cond:expr, $err:expr $(,)?) => {
+        if !$cond {
+            return Err($err);
+        }
+    };
+}
+
+pub mod curve;
+pub mod math;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+use core::fmt;
+
+pub use curve::{Curve, CurveType};
+use math::{amount_before_fee, mul_div_ceil, mul_div_floor, WAD};
+
+/// Basis points in 100%.
+pub const BASIS_POINTS_DIVISOR: u64 = 10_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuoteError {
+    InvalidAmount,
+    MathOverflow,
+    InsufficientLiquidity,
+}
+
+impl fmt::Display for QuoteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            QuoteError::InvalidAmount => "Invalid amount",
+            QuoteError::MathOverflow => "Math overflow",
+            QuoteError::InsufficientLiquidity => "Insufficient liquidity",
+        })
+    }
+}
+
+/// Everything a trade is priced against: the curve, its real reserves and
+/// the fees charged on top.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pool {
+    pub curve: Curve,
+    pub real_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    /// Platform trade fee, `PlatformConfig::trade_fee_bps`
+    pub trade_fee_bps: u64,
+    /// Creator royalty, `TokenConfig::creator_fee_bps`
+    pub creator_fee_bps: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+/// A priced trade. `sol_amount` is the curve side of the trade with fees
+/// included: what the buyer pays, or what the curve releases on a sell.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TradeQuote {
+    pub side: Side,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    /// Trade fee, split later between the platform, the creator and LPs
+    pub trade_fee: u64,
+    /// Per-token creator royalty
+    pub creator_fee: u64,
+}
+
+impl TradeQuote {
+    /// SOL reaching the curve on a buy, or the seller on a sell.
+    pub fn net_sol_amount(&self) -> u64 {
+        self.sol_amount - self.trade_fee - self.creator_fee
+    }
+
+    /// SOL moved in or out of the curve's reserves: the net amount on a
+    /// buy, the gross amount on a sell.
+    pub fn curve_sol_amount(&self) -> u64 {
+        match self.side {
+            Side::Buy => self.net_sol_amount(),
+            Side::Sell => self.sol_amount,
+        }
+    }
+}
+
+/// Splits the fees on a trade of `amount` lamports into the trade fee and
+/// the creator royalty. Their sum is rounded up and the royalty down, so the
+/// trade fee is never less than when charged alone.
+pub fn trade_fees(
+    amount: u64,
+    trade_fee_bps: u64,
+    creator_fee_bps: u64,
+) -> Result<(u64, u64), QuoteError> {
+    let total_fee = mul_div_ceil(
+        amount,
+        trade_fee_bps + creator_fee_bps,
+        BASIS_POINTS_DIVISOR,
+    )
+    .ok_or(QuoteError::MathOverflow)?;
+    let creator_fee = mul_div_floor(amount, creator_fee_bps, BASIS_POINTS_DIVISOR)
+        .ok_or(QuoteError::MathOverflow)?;
+    Ok((total_fee - creator_fee, creator_fee))
+}
+
+/// `buy_tokens`: spend `sol_amount` lamports, fees included.
+pub fn get_buy_quote(pool: &Pool, sol_amount: u64) -> Result<TradeQuote, QuoteError> {
+    ensure!(sol_amount > 0, QuoteError::InvalidAmount);
+
+    // Only the amount left after the fees reaches the curve
+    let (trade_fee, creator_fee) =
+        trade_fees(sol_amount, pool.trade_fee_bps, pool.creator_fee_bps)?;
+    let sol_to_curve = sol_amount
+        .checked_sub(trade_fee + creator_fee)
+        .ok_or(QuoteError::MathOverflow)?;
+    let token_amount = pool.curve.quote_buy(sol_to_curve)?;
+
+    ensure!(token_amount > 0, QuoteError::InvalidAmount);
+    check_token_reserves(pool, token_amount)?;
+
+    Ok(TradeQuote {
+        side: Side::Buy,
+        sol_amount,
+        token_amount,
+        trade_fee,
+        creator_fee,
+    })
+}
+
+/// `buy_exact_tokens_out`: receive exactly `token_amount` tokens.
+pub fn get_buy_exact_tokens_quote(
+    pool: &Pool,
+    token_amount: u64,
+) -> Result<TradeQuote, QuoteError> {
+    ensure!(token_amount > 0, QuoteError::InvalidAmount);
+
+    // Price the tokens on the curve, then gross up for the fees. The fees
+    // are the same `get_buy_quote` charges on `sol_amount`; any rounding
+    // surplus stays in the curve
+    let fee_bps = pool.trade_fee_bps + pool.creator_fee_bps;
+    let sol_to_curve = pool.curve.quote_buy_exact_tokens(token_amount)?;
+    let sol_amount = amount_before_fee(sol_to_curve, fee_bps, BASIS_POINTS_DIVISOR)
+        .ok_or(QuoteError::MathOverflow)?;
+    let (trade_fee, creator_fee) =
+        trade_fees(sol_amount, pool.trade_fee_bps, pool.creator_fee_bps)?;
+
+    check_token_reserves(pool, token_amount)?;
+
+    Ok(TradeQuote {
+        side: Side::Buy,
+        sol_amount,
+        token_amount,
+        trade_fee,
+        creator_fee,
+    })
+}
+
+/// `sell_tokens`: sell exactly `token_amount` tokens.
+pub fn get_sell_quote(pool: &Pool, token_amount: u64) -> Result<TradeQuote, QuoteError> {
+    ensure!(token_amount > 0, QuoteError::InvalidAmount);
+
+    let sol_amount = pool.curve.quote_sell(token_amount)?;
+    ensure!(sol_amount > 0, QuoteError::InvalidAmount);
+
+    let (trade_fee, creator_fee) =
+        trade_fees(sol_amount, pool.trade_fee_bps, pool.creator_fee_bps)?;
+
+    check_sol_reserves(pool, sol_amount)?;
+
+    Ok(TradeQuote {
+        side: Side::Sell,
+        sol_amount,
+        token_amount,
+        trade_fee,
+        creator_fee,
+    })
+}
+
+/// `sell_for_exact_sol`: receive exactly `sol_amount` lamports after fees.
+pub fn get_sell_for_exact_sol_quote(
+    pool: &Pool,
+    sol_amount: u64,
+) -> Result<TradeQuote, QuoteError> {
+    ensure!(sol_amount > 0, QuoteError::InvalidAmount);
+
+    // Gross up for the fees, then price the tokens on the curve. The fees
+    // are the same `get_sell_quote` charges; the curve releases only the
+    // seller's SOL plus the fees and keeps any rounding surplus
+    let fee_bps = pool.trade_fee_bps + pool.creator_fee_bps;
+    let sol_gross = amount_before_fee(sol_amount, fee_bps, BASIS_POINTS_DIVISOR)
+        .ok_or(QuoteError::MathOverflow)?;
+    let token_amount = pool.curve.quote_sell_exact_sol(sol_gross)?;
+    ensure!(token_amount > 0, QuoteError::InvalidAmount);
+
+    let (trade_fee, creator_fee) = trade_fees(sol_gross, pool.trade_fee_bps, pool.creator_fee_bps)?;
+    let sol_out = sol_amount
+        .checked_add(trade_fee + creator_fee)
+        .ok_or(QuoteError::MathOverflow)?;
+
+    check_sol_reserves(pool, sol_out)?;
+
+    Ok(TradeQuote {
+        side: Side::Sell,
+        sol_amount: sol_out,
+        token_amount,
+        trade_fee,
+        creator_fee,
+    })
+}
+
+/// Current marginal price, see [`Curve::spot_price`].
+pub fn spot_price(curve: &Curve) -> Result<u128, QuoteError> {
+    curve.spot_price()
+}
+
+/// Lamports `total_supply` tokens are worth at the current spot price.
+pub fn market_cap(curve: &Curve, total_supply: u64) -> Result<u64, QuoteError> {
+    let market_cap = curve
+        .spot_price()?
+        .checked_mul(total_supply as u128)
+        .ok_or(QuoteError::MathOverflow)?
+        / WAD;
+    u64::try_from(market_cap).map_err(|_| QuoteError::MathOverflow)
+}
+
+/// How far the trade's average price on the curve, fees excluded, is from
+/// the spot price before the trade, in basis points of the spot price.
+pub fn price_impact_bps(curve: &Curve, quote: &TradeQuote) -> Result<u64, QuoteError> {
+    ensure!(quote.token_amount > 0, QuoteError::InvalidAmount);
+    let spot = curve.spot_price()?;
+    let average = (quote.curve_sol_amount() as u128)
+        .checked_mul(WAD)
+        .ok_or(QuoteError::MathOverflow)?
+        / quote.token_amount as u128;
+    let impact = average
+        .abs_diff(spot)
+        .checked_mul(BASIS_POINTS_DIVISOR as u128)
+        .and_then(|v| v.checked_div(spot))
+        .ok_or(QuoteError::MathOverflow)?;
+    Ok(u64::try_from(impact).unwrap_or(u64::MAX))
+}
+
+/// Only tokens actually held by the curve can be bought.
+fn check_token_reserves(pool: &Pool, token_amount: u64) -> Result<(), QuoteError> {
+    ensure!(
+        token_amount <= pool.real_token_reserves,
+        QuoteError::InsufficientLiquidity
+    );
+    Ok(())
+}
+
+/// Only SOL actually deposited into the curve can be paid out.
+fn check_sol_reserves(pool: &Pool, sol_out: u64) -> Result<(), QuoteError> {
+    ensure!(
+        sol_out <= pool.real_sol_reserves,
+        QuoteError::InsufficientLiquidity
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOL: u64 = 1_000_000_000;
+    const TOKEN: u64 = 1_000_000_000;
+
+    fn pool(curve_type: CurveType) -> Pool {
+        Pool {
+            curve: Curve {
+                curve_type,
+                initial_virtual_sol_reserves: 30 * SOL,
+                initial_virtual_token_reserves: 1_073_000_000 * TOKEN,
+                virtual_sol_reserves: 30 * SOL,
+                virtual_token_reserves: 1_073_000_000 * TOKEN,
+                tokens_sold: 0,
+            },
+            real_sol_reserves: 0,
+            real_token_reserves: 800_000_000 * TOKEN,
+            trade_fee_bps: 100,
+            creator_fee_bps: 50,
+        }
+    }
+
+    #[test]
+    fn every_curve_starts_at_the_same_spot_price() {
+        let start = 30 * SOL as u128 * WAD / (1_073_000_000 * TOKEN) as u128;
+        for curve_type in [
+            CurveType::ConstantProduct,
+            CurveType::Linear,
+            CurveType::Exponential,
+        ] {
+            let curve = pool(curve_type).curve;
+            assert_eq!(spot_price(&curve).unwrap(), start);
+            // 1B tokens at ~27.96 lamports each
+            assert_eq!(
+                market_cap(&curve, 1_000_000_000 * TOKEN).unwrap(),
+                27_958_993_476
+            );
+        }
+    }
+
+    #[test]
+    fn buy_quote_charges_fees_before_the_curve() {
+        let pool = pool(CurveType::ConstantProduct);
+        let quote = get_buy_quote(&pool, SOL).unwrap();
+        assert_eq!(
+            (quote.trade_fee, quote.creator_fee),
+            (10_000_000, 5_000_000)
+        );
+        assert_eq!(quote.net_sol_amount(), SOL - 15_000_000);
+        assert_eq!(
+            quote.token_amount,
+            pool.curve.quote_buy(quote.net_sol_amount()).unwrap()
+        );
+        assert_eq!(get_buy_quote(&pool, 0), Err(QuoteError::InvalidAmount));
+    }
+
+    #[test]
+    fn sells_are_bounded_by_real_reserves() {
+        let mut pool = pool(CurveType::ConstantProduct);
+        let bought = get_buy_quote(&pool, SOL).unwrap();
+        pool.curve.virtual_sol_reserves += bought.net_sol_amount();
+        pool.curve.virtual_token_reserves -= bought.token_amount;
+        pool.curve.tokens_sold += bought.token_amount;
+
+        // The curve only holds what the buy deposited
+        assert_eq!(
+            get_sell_quote(&pool, bought.token_amount),
+            Err(QuoteError::InsufficientLiquidity)
+        );
+        pool.real_sol_reserves = bought.net_sol_amount();
+        let sold = get_sell_quote(&pool, bought.token_amount).unwrap();
+        assert!(sold.net_sol_amount() < bought.sol_amount);
+    }
+
+    #[test]
+    fn price_impact_grows_with_trade_size() {
+        let pool = pool(CurveType::ConstantProduct);
+        let small = get_buy_quote(&pool, SOL / 100).unwrap();
+        let large = get_buy_quote(&pool, 10 * SOL).unwrap();
+        let small_impact = price_impact_bps(&pool.curve, &small).unwrap();
+        let large_impact = price_impact_bps(&pool.curve, &large).unwrap();
+        assert!(small_impact < 10);
+        // Buying 10 SOL into 30 SOL of virtual reserves averages ~1.33x spot
+        assert!((3_200..3_400).contains(&large_impact));
+    }
+}
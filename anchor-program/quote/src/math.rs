@@ -0,0 +1,188 @@
+//! Pure integer math shared by the curve and the instruction handlers.
+//!
+//! Everything is computed with u128 intermediates. Each helper states its
+//! rounding direction so callers can always round in favour of the pool.
+
+/// Fixed-point scale used by `exp_wad` and `ln_wad`.
+pub const WAD: u128 = 1_000_000_000_000_000_000;
+/// ln(2) scaled by `WAD`, rounded down.
+const LN_2_WAD: u128 = 693_147_180_559_945_309;
+/// Largest power of two `exp_wad_*` can scale its result by without overflow.
+const MAX_EXP_SHIFT: u128 = 66;
+/// Bound on the error of the series in `exp_wad_*`, in units of `1 / WAD`,
+/// before the result is scaled by `2^k`.
+const EXP_ERROR_BOUND: u128 = 256;
+
+/// `a * b / c`, rounded down. `None` if `c` is zero or the result exceeds u64.
+pub fn mul_div_floor(a: u64, b: u64, c: u64) -> Option<u64> {
+    let result = (a as u128 * b as u128).checked_div(c as u128)?;
+    u64::try_from(result).ok()
+}
+
+/// `a * b / c`, rounded up. `None` if `c` is zero or the result exceeds u64.
+pub fn mul_div_ceil(a: u64, b: u64, c: u64) -> Option<u64> {
+    let result = div_ceil(a as u128 * b as u128, c as u128)?;
+    u64::try_from(result).ok()
+}
+
+/// `a / b`, rounded up. `None` if `b` is zero.
+pub fn div_ceil(a: u128, b: u128) -> Option<u128> {
+    if b == 0 {
+        return None;
+    }
+    Some(a.div_ceil(b))
+}
+
+/// Smallest-ish `gross` such that `gross` minus a `fee_bps` fee (rounded up,
+/// as charged on trades) still leaves at least `net`.
+pub fn amount_before_fee(net: u64, fee_bps: u64, divisor: u64) -> Option<u64> {
+    let mut gross = mul_div_ceil(net, divisor, divisor.checked_sub(fee_bps)?)?;
+    while gross - mul_div_ceil(gross, fee_bps, divisor)? < net {
+        gross = gross.checked_add(1)?;
+    }
+    Some(gross)
+}
+
+/// Floor of the square root of `n`.
+pub fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    let mut x = 1u128 << (128 - n.leading_zeros()).div_ceil(2);
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// Lower bound of `e^x` for a non-negative `WAD`-scaled `x`, or `None` on
+/// overflow.
+pub fn exp_wad_down(x: u128) -> Option<u128> {
+    let (sum, k) = exp_series(x)?;
+    Some(sum.saturating_sub(EXP_ERROR_BOUND).max(WAD) << k)
+}
+
+/// Upper bound of `e^x` for a non-negative `WAD`-scaled `x`, or `None` on
+/// overflow.
+pub fn exp_wad_up(x: u128) -> Option<u128> {
+    let (sum, k) = exp_series(x)?;
+    Some((sum + EXP_ERROR_BOUND) << k)
+}
+
+/// `e^x = 2^k * e^r` with `r` in `[0, ln 2)`; returns `(e^r, k)`.
+fn exp_series(x: u128) -> Option<(u128, u128)> {
+    let k = x / LN_2_WAD;
+    if k > MAX_EXP_SHIFT {
+        return None;
+    }
+    let r = x - k * LN_2_WAD;
+
+    let mut term = WAD;
+    let mut sum = WAD;
+    let mut i = 1;
+    while term > 0 {
+        term = term * r / WAD / i;
+        sum += term;
+        i += 1;
+    }
+    Some((sum, k))
+}
+
+/// Lower bound of the natural log of a `WAD`-scaled `x`, scaled by `WAD`.
+/// Returns zero for `x <= 1`.
+pub fn ln_wad_down(x: u128) -> u128 {
+    if x <= WAD {
+        return 0;
+    }
+    // ln x = k * ln 2 + ln y with y in [1, 2)
+    let k = (x / WAD).ilog2() as u128;
+    let y = x >> k;
+
+    // ln y = 2 * atanh(z) with z = (y - 1) / (y + 1) <= 1/3; every step
+    // rounds down and the series is truncated, so the result only undershoots
+    let z = (y - WAD) * WAD / (y + WAD);
+    let z_squared = z * z / WAD;
+    let mut term = z;
+    let mut sum = z;
+    let mut n = 3;
+    while term > 0 {
+        term = term * z_squared / WAD;
+        sum += term / n;
+        n += 2;
+    }
+    k * LN_2_WAD + 2 * sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn to_f64(wad: u128) -> f64 {
+        wad as f64 / WAD as f64
+    }
+
+    #[test]
+    fn exp_and_ln_match_known_values() {
+        assert_eq!(exp_wad_down(0), Some(WAD));
+        assert_eq!(ln_wad_down(WAD), 0);
+        let e = exp_wad_down(WAD).unwrap();
+        assert!((to_f64(e) - std::f64::consts::E).abs() < 1e-12);
+        let ln_2 = ln_wad_down(2 * WAD);
+        assert!(ln_2 <= LN_2_WAD);
+        assert!(LN_2_WAD - ln_2 < 1_000);
+        assert_eq!(exp_wad_down((MAX_EXP_SHIFT + 1) * LN_2_WAD), None);
+    }
+
+    proptest! {
+        #[test]
+        fn mul_div_brackets_the_exact_quotient(a: u64, b: u64, c in 1..=u64::MAX) {
+            let exact = a as u128 * b as u128;
+            if let Some(floor) = mul_div_floor(a, b, c) {
+                prop_assert!(floor as u128 * c as u128 <= exact);
+                prop_assert!((floor as u128 + 1) * c as u128 > exact);
+            }
+            if let Some(ceil) = mul_div_ceil(a, b, c) {
+                prop_assert!(ceil as u128 * c as u128 >= exact);
+                prop_assert!(mul_div_floor(a, b, c).unwrap() + !exact.is_multiple_of(c as u128) as u64 == ceil);
+            }
+        }
+
+        #[test]
+        fn amount_before_fee_covers_the_fee(net in 0..u64::MAX / 2, fee_bps in 0..=1_000u64) {
+            let gross = amount_before_fee(net, fee_bps, 10_000).unwrap();
+            let fee = mul_div_ceil(gross, fee_bps, 10_000).unwrap();
+            prop_assert!(gross - fee >= net);
+            prop_assert!(gross - fee <= net + 1);
+        }
+
+        #[test]
+        fn isqrt_is_the_floor_square_root(n: u128) {
+            let root = isqrt(n);
+            prop_assert!(root * root <= n);
+            prop_assert!((root + 1).checked_mul(root + 1).is_none_or(|sq| sq > n));
+        }
+
+        #[test]
+        fn exp_bounds_bracket_the_true_value(x in 0..40 * WAD) {
+            let down = exp_wad_down(x).unwrap();
+            let up = exp_wad_up(x).unwrap();
+            let expected = to_f64(x).exp();
+            prop_assert!(down <= up);
+            prop_assert!(to_f64(down) <= expected * (1.0 + 1e-12));
+            prop_assert!(to_f64(up) >= expected * (1.0 - 1e-12));
+            prop_assert!((to_f64(up) - to_f64(down)) / expected < 1e-12);
+        }
+
+        #[test]
+        fn ln_undershoots_and_inverts_exp(x in 0..40 * WAD) {
+            let e = exp_wad_down(x).unwrap();
+            let ln = ln_wad_down(e);
+            prop_assert!(ln <= x);
+            prop_assert!(x - ln <= x / 1_000_000_000_000 + 1_000);
+        }
+    }
+}
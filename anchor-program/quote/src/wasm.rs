@@ -0,0 +1,151 @@
+//! JavaScript bindings for the web app.
+//!
+//! Amounts cross the boundary as `bigint`. Build with:
+//!
+//! ```bash
+//! cargo rustc -p audio_token_quote --lib --release --features wasm \
+//!     --target wasm32-unknown-unknown --crate-type cdylib
+//! wasm-bindgen --target web --out-dir pkg \
+//!     target/wasm32-unknown-unknown/release/audio_token_quote.wasm
+//! ```
+
+use wasm_bindgen::prelude::*;
+
+use crate::{Curve, CurveType, Pool, QuoteError, TradeQuote};
+
+impl From<QuoteError> for JsError {
+    fn from(error: QuoteError) -> Self {
+        JsError::new(&error.to_string())
+    }
+}
+
+#[wasm_bindgen(js_name = CurveType)]
+#[derive(Clone, Copy)]
+pub enum JsCurveType {
+    ConstantProduct,
+    Linear,
+    Exponential,
+}
+
+impl From<JsCurveType> for CurveType {
+    fn from(curve_type: JsCurveType) -> Self {
+        match curve_type {
+            JsCurveType::ConstantProduct => CurveType::ConstantProduct,
+            JsCurveType::Linear => CurveType::Linear,
+            JsCurveType::Exponential => CurveType::Exponential,
+        }
+    }
+}
+
+/// A token's pool, built from its fetched `TokenConfig` and the platform's
+/// `trade_fee_bps`.
+#[wasm_bindgen(js_name = Pool)]
+pub struct JsPool(Pool);
+
+#[wasm_bindgen(js_class = Pool)]
+impl JsPool {
+    #[wasm_bindgen(constructor)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        curve_type: JsCurveType,
+        initial_virtual_sol_reserves: u64,
+        initial_virtual_token_reserves: u64,
+        virtual_sol_reserves: u64,
+        virtual_token_reserves: u64,
+        tokens_sold: u64,
+        real_sol_reserves: u64,
+        real_token_reserves: u64,
+        trade_fee_bps: u64,
+        creator_fee_bps: u64,
+    ) -> JsPool {
+        JsPool(Pool {
+            curve: Curve {
+                curve_type: curve_type.into(),
+                initial_virtual_sol_reserves,
+                initial_virtual_token_reserves,
+                virtual_sol_reserves,
+                virtual_token_reserves,
+                tokens_sold,
+            },
+            real_sol_reserves,
+            real_token_reserves,
+            trade_fee_bps,
+            creator_fee_bps,
+        })
+    }
+
+    #[wasm_bindgen(js_name = getBuyQuote)]
+    pub fn get_buy_quote(&self, sol_amount: u64) -> Result<JsTradeQuote, JsError> {
+        Ok(JsTradeQuote(crate::get_buy_quote(&self.0, sol_amount)?))
+    }
+
+    #[wasm_bindgen(js_name = getBuyExactTokensQuote)]
+    pub fn get_buy_exact_tokens_quote(&self, token_amount: u64) -> Result<JsTradeQuote, JsError> {
+        Ok(JsTradeQuote(crate::get_buy_exact_tokens_quote(
+            &self.0,
+            token_amount,
+        )?))
+    }
+
+    #[wasm_bindgen(js_name = getSellQuote)]
+    pub fn get_sell_quote(&self, token_amount: u64) -> Result<JsTradeQuote, JsError> {
+        Ok(JsTradeQuote(crate::get_sell_quote(&self.0, token_amount)?))
+    }
+
+    #[wasm_bindgen(js_name = getSellForExactSolQuote)]
+    pub fn get_sell_for_exact_sol_quote(&self, sol_amount: u64) -> Result<JsTradeQuote, JsError> {
+        Ok(JsTradeQuote(crate::get_sell_for_exact_sol_quote(
+            &self.0, sol_amount,
+        )?))
+    }
+
+    /// SOL per whole token, scaled by 10^18.
+    #[wasm_bindgen(js_name = spotPrice)]
+    pub fn spot_price(&self) -> Result<u128, JsError> {
+        Ok(crate::spot_price(&self.0.curve)?)
+    }
+
+    /// Lamports.
+    #[wasm_bindgen(js_name = marketCap)]
+    pub fn market_cap(&self, total_supply: u64) -> Result<u64, JsError> {
+        Ok(crate::market_cap(&self.0.curve, total_supply)?)
+    }
+
+    #[wasm_bindgen(js_name = priceImpactBps)]
+    pub fn price_impact_bps(&self, quote: &JsTradeQuote) -> Result<u64, JsError> {
+        Ok(crate::price_impact_bps(&self.0.curve, &quote.0)?)
+    }
+}
+
+#[wasm_bindgen(js_name = TradeQuote)]
+pub struct JsTradeQuote(TradeQuote);
+
+#[wasm_bindgen(js_class = TradeQuote)]
+impl JsTradeQuote {
+    /// Paid by the buyer, or released by the curve on a sell, fees included.
+    #[wasm_bindgen(getter, js_name = solAmount)]
+    pub fn sol_amount(&self) -> u64 {
+        self.0.sol_amount
+    }
+
+    /// Reaching the curve on a buy, or the seller on a sell.
+    #[wasm_bindgen(getter, js_name = netSolAmount)]
+    pub fn net_sol_amount(&self) -> u64 {
+        self.0.net_sol_amount()
+    }
+
+    #[wasm_bindgen(getter, js_name = tokenAmount)]
+    pub fn token_amount(&self) -> u64 {
+        self.0.token_amount
+    }
+
+    #[wasm_bindgen(getter, js_name = tradeFee)]
+    pub fn trade_fee(&self) -> u64 {
+        self.0.trade_fee
+    }
+
+    #[wasm_bindgen(getter, js_name = creatorFee)]
+    pub fn creator_fee(&self) -> u64 {
+        self.0.creator_fee
+    }
+}